@@ -0,0 +1,503 @@
+use crate::menu::MenuState;
+use crate::turn_events::match_enemies;
+use crate::types::*;
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+use chrono::Local;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+
+const FOOD_TYPES: [FoodType; 3] = [FoodType::Apple, FoodType::Bread, FoodType::Nectar];
+const ANT_TYPES: [AntType; 3] = [AntType::Worker, AntType::Soldier, AntType::Scout];
+const CHART_HEIGHT: f32 = 110.0;
+
+// Everything we track for one turn of the round
+#[derive(Debug, Clone, Default)]
+pub struct TurnStats {
+    pub turn: i32,
+    pub score: i32,
+    pub calories: HashMap<FoodType, i32>, // Delivered this turn
+    pub ants: HashMap<AntType, usize>,
+    pub deaths: u32,
+    pub damage_dealt: i32, // Enemy HP lost on hexes our ants struck, kills count the HP left
+    pub damage_taken: i32,
+    pub food_collected: i32,
+    pub food_delivered: i32,
+    pub visible_tiles: usize,
+    pub explored_tiles: usize, // Every hex seen so far this round
+}
+
+impl TurnStats {
+    fn calories_of(&self, food_type: FoodType) -> i32 {
+        self.calories.get(&food_type).copied().unwrap_or(0)
+    }
+
+    fn ants_of(&self, ant_type: AntType) -> usize {
+        self.ants.get(&ant_type).copied().unwrap_or(0)
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Analytics {
+    turns: Vec<TurnStats>,
+    pending: TurnStats, // Events collected for the turn that has not been recorded yet
+    explored: HashSet<HexCoord>,
+    last_turn: Option<i32>,
+    previous_enemies: Vec<(AntType, HexCoord, i32)>, // Type, position and health last turn
+}
+
+impl Analytics {
+    pub fn turns(&self) -> &[TurnStats] {
+        &self.turns
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("turn,score");
+        for food_type in FOOD_TYPES {
+            let _ = write!(csv, ",calories_{:?}", food_type);
+        }
+        for ant_type in ANT_TYPES {
+            let _ = write!(csv, ",ants_{:?}", ant_type);
+        }
+        csv.push_str(
+            ",deaths,damage_dealt,damage_taken,food_collected,food_delivered,visible_tiles,explored_tiles\n",
+        );
+
+        for stats in &self.turns {
+            let _ = write!(csv, "{},{}", stats.turn, stats.score);
+            for food_type in FOOD_TYPES {
+                let _ = write!(csv, ",{}", stats.calories_of(food_type));
+            }
+            for ant_type in ANT_TYPES {
+                let _ = write!(csv, ",{}", stats.ants_of(ant_type));
+            }
+            let _ = writeln!(
+                csv,
+                ",{},{},{},{},{},{},{}",
+                stats.deaths,
+                stats.damage_dealt,
+                stats.damage_taken,
+                stats.food_collected,
+                stats.food_delivered,
+                stats.visible_tiles,
+                stats.explored_tiles
+            );
+        }
+        csv
+    }
+
+    pub fn export_csv(&self) -> Option<PathBuf> {
+        if self.turns.is_empty() {
+            return None;
+        }
+
+        let date = Local::now().format("%Y-%m-%d_%H-%M-%S").to_string();
+        let path = PathBuf::from("logs").join(format!("{}_analytics.csv", date));
+        let result = fs::create_dir_all("logs").and_then(|_| fs::write(&path, self.to_csv()));
+        match result {
+            Ok(()) => {
+                info!(
+                    "Exported analytics for {} turns to {}",
+                    self.turns.len(),
+                    path.display()
+                );
+                Some(path)
+            }
+            Err(e) => {
+                warn!("Failed to export analytics to {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    fn record_turn(&mut self, game_state: &GameState) {
+        self.explored
+            .extend(game_state.visible_tiles.keys().copied());
+
+        let mut stats = std::mem::take(&mut self.pending);
+        stats.turn = game_state.turn_number;
+        stats.score = game_state.score;
+        stats.visible_tiles = game_state.visible_tiles.len();
+        stats.explored_tiles = self.explored.len();
+        for ant in game_state.my_ants.values() {
+            *stats.ants.entry(ant.ant_type).or_insert(0) += 1;
+        }
+        stats.damage_dealt = self.damage_dealt(game_state);
+        self.turns.push(stats);
+
+        self.previous_enemies = game_state
+            .enemy_ants
+            .values()
+            .map(|enemy| (enemy.ant_type, enemy.position, enemy.health))
+            .collect();
+    }
+
+    // Follows enemies from last turn to this one, so supports and anthill bonuses are included
+    // and damage only counts once it landed
+    fn damage_dealt(&self, game_state: &GameState) -> i32 {
+        let struck: HashSet<HexCoord> = game_state
+            .my_ants
+            .values()
+            .filter_map(|ant| ant.last_attack)
+            .collect();
+        if struck.is_empty() {
+            return 0;
+        }
+
+        let previous: Vec<(AntType, HexCoord)> = self
+            .previous_enemies
+            .iter()
+            .map(|(ant_type, position, _)| (*ant_type, *position))
+            .collect();
+        let current: Vec<&Enemy> = game_state.enemy_ants.values().collect();
+        let matches = match_enemies(&previous, &current);
+
+        let mut damage = 0;
+        for (enemy, matched) in current.iter().zip(&matches) {
+            if let Some(index) = matched {
+                if struck.contains(&enemy.position) {
+                    damage += (self.previous_enemies[*index].2 - enemy.health).max(0);
+                }
+            }
+        }
+
+        // A struck hex left empty is a kill by the closest enemy no one else accounts for
+        let mut unmatched: Vec<usize> = (0..previous.len())
+            .filter(|index| !matches.contains(&Some(*index)))
+            .collect();
+        for hex in &struck {
+            if current.iter().any(|enemy| enemy.position == *hex) {
+                continue;
+            }
+            let killed = unmatched
+                .iter()
+                .enumerate()
+                .filter(|(_, index)| {
+                    let (ant_type, position, _) = self.previous_enemies[**index];
+                    position.distance_to(hex) <= ant_type.speed()
+                })
+                .min_by_key(|(_, index)| self.previous_enemies[**index].1.distance_to(hex))
+                .map(|(slot, _)| slot);
+            if let Some(slot) = killed {
+                damage += self.previous_enemies[unmatched.swap_remove(slot)].2;
+            }
+        }
+
+        damage
+    }
+}
+
+pub fn record_analytics(
+    game_state: Res<GameState>,
+    mut analytics: ResMut<Analytics>,
+    mut died: EventReader<AntDiedEvent>,
+    mut damaged: EventReader<AntDamagedEvent>,
+    mut collected: EventReader<FoodCollectedEvent>,
+    mut delivered: EventReader<FoodDeliveredEvent>,
+    mut round_ended: EventReader<RoundEndedEvent>,
+) {
+    let pending = &mut analytics.pending;
+    pending.deaths += died.read().count() as u32;
+    pending.damage_taken += damaged.read().map(|event| event.damage).sum::<i32>();
+    pending.food_collected += collected.read().map(|event| event.amount).sum::<i32>();
+    for event in delivered.read() {
+        pending.food_delivered += event.amount;
+        *pending.calories.entry(event.food_type).or_insert(0) += event.calories;
+    }
+
+    // Keep the round's numbers for review, whichever way it ended
+    if round_ended.read().count() > 0 {
+        analytics.export_csv();
+    }
+
+    if !game_state.connected || analytics.last_turn == Some(game_state.turn_number) {
+        return;
+    }
+
+    // A lower turn number means a new round, it was exported when the old one ended
+    if analytics
+        .last_turn
+        .is_some_and(|turn| game_state.turn_number < turn)
+    {
+        *analytics = Analytics::default();
+    }
+
+    analytics.last_turn = Some(game_state.turn_number);
+    analytics.record_turn(&game_state);
+}
+
+pub fn analytics_panel_system(
+    mut contexts: EguiContexts,
+    mut menu_state: ResMut<MenuState>,
+    analytics: Res<Analytics>,
+) -> Result {
+    if !menu_state.show_analytics_panel {
+        return Ok(());
+    }
+
+    let ctx = contexts.ctx_mut()?;
+    let mut open = menu_state.show_analytics_panel;
+    let turns = analytics.turns();
+
+    egui::SidePanel::right("analytics_panel")
+        .resizable(true)
+        .default_width(380.0)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.heading("Analytics");
+                if ui.button("Export CSV").clicked() {
+                    analytics.export_csv();
+                }
+                if ui.button("Close").clicked() {
+                    open = false;
+                }
+            });
+
+            let Some(last) = turns.last() else {
+                ui.label("No turns recorded yet");
+                return;
+            };
+            let total_calories: i32 = turns.iter().flat_map(|stats| stats.calories.values()).sum();
+            let total_deaths: u32 = turns.iter().map(|stats| stats.deaths).sum();
+            ui.label(format!(
+                "Turn {}  Score {}  Calories {}  Deaths {}",
+                last.turn, last.score, total_calories, total_deaths
+            ));
+            ui.separator();
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                ui.strong("Score");
+                line_chart(
+                    ui,
+                    &[(
+                        "Score",
+                        egui::Color32::GOLD,
+                        series(turns, |s| s.score as f32),
+                    )],
+                );
+
+                ui.strong("Calories delivered by food type");
+                let calories: Vec<(&str, egui::Color32, Vec<f32>)> = FOOD_TYPES
+                    .iter()
+                    .map(|food_type| {
+                        (
+                            food_label(*food_type),
+                            food_color(*food_type),
+                            series(turns, |s| s.calories_of(*food_type) as f32),
+                        )
+                    })
+                    .collect();
+                stacked_chart(ui, &calories);
+
+                ui.strong("Ants by type");
+                let ants: Vec<(&str, egui::Color32, Vec<f32>)> = ANT_TYPES
+                    .iter()
+                    .map(|ant_type| {
+                        (
+                            ant_label(*ant_type),
+                            ant_color(*ant_type),
+                            series(turns, |s| s.ants_of(*ant_type) as f32),
+                        )
+                    })
+                    .collect();
+                stacked_chart(ui, &ants);
+
+                ui.strong("Damage dealt vs taken");
+                line_chart(
+                    ui,
+                    &[
+                        (
+                            "Dealt",
+                            egui::Color32::LIGHT_GREEN,
+                            series(turns, |s| s.damage_dealt as f32),
+                        ),
+                        (
+                            "Taken",
+                            egui::Color32::LIGHT_RED,
+                            series(turns, |s| s.damage_taken as f32),
+                        ),
+                        (
+                            "Deaths",
+                            egui::Color32::RED,
+                            series(turns, |s| s.deaths as f32),
+                        ),
+                    ],
+                );
+
+                ui.strong("Food collected vs delivered");
+                line_chart(
+                    ui,
+                    &[
+                        (
+                            "Collected",
+                            egui::Color32::LIGHT_BLUE,
+                            series(turns, |s| s.food_collected as f32),
+                        ),
+                        (
+                            "Delivered",
+                            egui::Color32::GREEN,
+                            series(turns, |s| s.food_delivered as f32),
+                        ),
+                    ],
+                );
+
+                ui.strong("Map coverage");
+                line_chart(
+                    ui,
+                    &[
+                        (
+                            "Visible",
+                            egui::Color32::LIGHT_GRAY,
+                            series(turns, |s| s.visible_tiles as f32),
+                        ),
+                        (
+                            "Explored",
+                            egui::Color32::WHITE,
+                            series(turns, |s| s.explored_tiles as f32),
+                        ),
+                    ],
+                );
+            });
+        });
+
+    menu_state.show_analytics_panel = open;
+
+    Ok(())
+}
+
+fn series(turns: &[TurnStats], value: impl Fn(&TurnStats) -> f32) -> Vec<f32> {
+    turns.iter().map(value).collect()
+}
+
+// Chart frame with a max label; returns the painter and the plotting rect
+fn chart_frame(ui: &mut egui::Ui, max: f32) -> (egui::Painter, egui::Rect) {
+    let size = egui::vec2(ui.available_width(), CHART_HEIGHT);
+    let (response, painter) = ui.allocate_painter(size, egui::Sense::hover());
+    let rect = response.rect;
+    painter.rect_stroke(
+        rect,
+        0.0,
+        egui::Stroke::new(1.0, egui::Color32::DARK_GRAY),
+        egui::StrokeKind::Inside,
+    );
+    painter.text(
+        rect.left_top() + egui::vec2(4.0, 2.0),
+        egui::Align2::LEFT_TOP,
+        format!("{:.0}", max),
+        egui::FontId::monospace(10.0),
+        egui::Color32::GRAY,
+    );
+    (painter, rect.shrink(4.0))
+}
+
+fn to_screen(rect: egui::Rect, index: usize, len: usize, value: f32, max: f32) -> egui::Pos2 {
+    let x = if len > 1 {
+        rect.left() + rect.width() * index as f32 / (len - 1) as f32
+    } else {
+        rect.center().x
+    };
+    let y = rect.bottom() - rect.height() * (value / max.max(1.0)).clamp(0.0, 1.0);
+    egui::pos2(x, y)
+}
+
+fn chart_legend(ui: &mut egui::Ui, lines: &[(&str, egui::Color32, Vec<f32>)]) {
+    ui.horizontal_wrapped(|ui| {
+        for (label, color, values) in lines {
+            let last = values.last().copied().unwrap_or(0.0);
+            ui.colored_label(*color, format!("{} {:.0}", label, last));
+        }
+    });
+}
+
+fn line_chart(ui: &mut egui::Ui, lines: &[(&str, egui::Color32, Vec<f32>)]) {
+    let max = lines
+        .iter()
+        .flat_map(|(_, _, values)| values.iter().copied())
+        .fold(0.0, f32::max);
+    let (painter, rect) = chart_frame(ui, max);
+
+    for (_, color, values) in lines {
+        let points: Vec<egui::Pos2> = values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| to_screen(rect, index, values.len(), *value, max))
+            .collect();
+        painter.line(points, egui::Stroke::new(1.5, *color));
+    }
+    chart_legend(ui, lines);
+}
+
+// Layers stacked bottom to top in the given order
+fn stacked_chart(ui: &mut egui::Ui, layers: &[(&str, egui::Color32, Vec<f32>)]) {
+    let len = layers
+        .iter()
+        .map(|(_, _, values)| values.len())
+        .max()
+        .unwrap_or(0);
+    let value = |values: &Vec<f32>, index: usize| values.get(index).copied().unwrap_or(0.0);
+
+    let mut base = vec![0.0; len];
+    let mut tops = Vec::with_capacity(layers.len());
+    for (_, _, values) in layers {
+        let top: Vec<f32> = (0..len)
+            .map(|index| base[index] + value(values, index))
+            .collect();
+        tops.push((base.clone(), top.clone()));
+        base = top;
+    }
+    let max = base.iter().copied().fold(0.0, f32::max);
+    let (painter, rect) = chart_frame(ui, max);
+
+    for ((_, color, _), (bottom, top)) in layers.iter().zip(&tops) {
+        let fill = color.gamma_multiply(0.6);
+        // One convex quad per turn step keeps the fill valid for any shape
+        for index in 1..len {
+            let quad = vec![
+                to_screen(rect, index - 1, len, bottom[index - 1], max),
+                to_screen(rect, index - 1, len, top[index - 1], max),
+                to_screen(rect, index, len, top[index], max),
+                to_screen(rect, index, len, bottom[index], max),
+            ];
+            painter.add(egui::Shape::convex_polygon(quad, fill, egui::Stroke::NONE));
+        }
+        let outline: Vec<egui::Pos2> = (0..len)
+            .map(|index| to_screen(rect, index, len, top[index], max))
+            .collect();
+        painter.line(outline, egui::Stroke::new(1.0, *color));
+    }
+    chart_legend(ui, layers);
+}
+
+fn food_label(food_type: FoodType) -> &'static str {
+    match food_type {
+        FoodType::Apple => "Apple",
+        FoodType::Bread => "Bread",
+        FoodType::Nectar => "Nectar",
+    }
+}
+
+fn food_color(food_type: FoodType) -> egui::Color32 {
+    match food_type {
+        FoodType::Apple => egui::Color32::from_rgb(220, 60, 60),
+        FoodType::Bread => egui::Color32::from_rgb(210, 170, 90),
+        FoodType::Nectar => egui::Color32::from_rgb(240, 200, 40),
+    }
+}
+
+fn ant_label(ant_type: AntType) -> &'static str {
+    match ant_type {
+        AntType::Worker => "Worker",
+        AntType::Soldier => "Soldier",
+        AntType::Scout => "Scout",
+    }
+}
+
+fn ant_color(ant_type: AntType) -> egui::Color32 {
+    match ant_type {
+        AntType::Worker => egui::Color32::from_rgb(90, 160, 255),
+        AntType::Soldier => egui::Color32::from_rgb(255, 110, 80),
+        AntType::Scout => egui::Color32::from_rgb(120, 220, 120),
+    }
+}
@@ -0,0 +1,238 @@
+use crate::influence::InfluenceMap;
+use crate::types::*;
+use bevy::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+
+// How long the pathfinder steers around a hex we failed to enter
+const ENEMY_OBSTACLE_TURNS: i32 = 1; // Enemies move, only trust it for the next turn
+const UNKNOWN_OBSTACLE_TURNS: i32 = 5;
+
+// What happened to the path an ant was sent on last turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionOutcome {
+    Completed,
+    BlockedByEnemy(HexCoord),
+    BlockedByFriendly(HexCoord), // A unit of the same type already held the hex
+    MpExhausted,
+    Blocked(HexCoord), // Stopped short for a reason we cannot see
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExecutionStats {
+    pub completed: u32,
+    pub blocked_by_enemy: u32,
+    pub blocked_by_friendly: u32,
+    pub mp_exhausted: u32,
+    pub blocked: u32,
+}
+
+impl ExecutionStats {
+    pub fn total(&self) -> u32 {
+        self.completed
+            + self.blocked_by_enemy
+            + self.blocked_by_friendly
+            + self.mp_exhausted
+            + self.blocked
+    }
+
+    pub fn success_rate(&self) -> f32 {
+        let total = self.total();
+        if total == 0 {
+            return 0.0;
+        }
+        self.completed as f32 / total as f32
+    }
+
+    fn add(&mut self, outcome: ExecutionOutcome) {
+        match outcome {
+            ExecutionOutcome::Completed => self.completed += 1,
+            ExecutionOutcome::BlockedByEnemy(_) => self.blocked_by_enemy += 1,
+            ExecutionOutcome::BlockedByFriendly(_) => self.blocked_by_friendly += 1,
+            ExecutionOutcome::MpExhausted => self.mp_exhausted += 1,
+            ExecutionOutcome::Blocked(_) => self.blocked += 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SentMove {
+    turn: i32,
+    strategy: &'static str,
+    from: HexCoord,
+    path: Vec<HexCoord>,
+    enemies: Vec<HexCoord>, // Enemy positions when the move was planned
+}
+
+// Compares the moves we sent with where the ants actually ended up
+#[derive(Resource, Default)]
+pub struct ExecutionAuditor {
+    sent: HashMap<String, SentMove>,
+    stats: BTreeMap<&'static str, ExecutionStats>,
+    last_turn: Option<i32>,
+}
+
+impl ExecutionAuditor {
+    pub fn stats(&self) -> &BTreeMap<&'static str, ExecutionStats> {
+        &self.stats
+    }
+
+    fn classify(sent: &SentMove, ant: &Ant, game_state: &GameState) -> ExecutionOutcome {
+        if sent.path.last() == Some(&ant.position) {
+            return ExecutionOutcome::Completed;
+        }
+
+        // The first hex of the path the ant did not get onto. lastMove may or may not list
+        // the hex the ant started from
+        let walked: Vec<&HexCoord> = ant
+            .last_move
+            .iter()
+            .skip_while(|pos| **pos == sent.from)
+            .collect();
+        let reached = if walked.is_empty() {
+            sent.path
+                .iter()
+                .position(|pos| *pos == ant.position)
+                .map_or(0, |index| index + 1)
+        } else {
+            walked
+                .iter()
+                .zip(&sent.path)
+                .take_while(|(walked, planned)| **walked == *planned)
+                .count()
+        };
+        let Some(next) = sent.path.get(reached).copied() else {
+            return ExecutionOutcome::Completed;
+        };
+
+        let enemy_there = sent.enemies.contains(&next)
+            || game_state
+                .enemy_ants
+                .values()
+                .any(|enemy| enemy.position == next);
+        if enemy_there {
+            return ExecutionOutcome::BlockedByEnemy(next);
+        }
+
+        let friendly_there = game_state.my_ants.values().any(|other| {
+            other.id != ant.id && other.ant_type == ant.ant_type && other.position == next
+        });
+        if friendly_there {
+            return ExecutionOutcome::BlockedByFriendly(next);
+        }
+
+        // Unexplored hexes are planned as plain, dirt costs double
+        let cost: i32 = sent.path[..=reached]
+            .iter()
+            .map(|pos| {
+                game_state
+                    .visible_tiles
+                    .get(pos)
+                    .and_then(|tile| tile.tile_type.movement_cost())
+                    .unwrap_or(1)
+            })
+            .sum();
+        if cost > ant.ant_type.speed() {
+            return ExecutionOutcome::MpExhausted;
+        }
+
+        ExecutionOutcome::Blocked(next)
+    }
+}
+
+pub fn audit_plan_execution(
+    game_state: Res<GameState>,
+    mut auditor: ResMut<ExecutionAuditor>,
+    mut influence: ResMut<InfluenceMap>,
+    mut decision_events: EventReader<StrategyDecisionEvent>,
+    mut move_events: EventReader<MoveCommandEvent>,
+) {
+    // Moves sent since the last run, tagged with the strategy that produced them
+    let decisions: HashMap<&String, &StrategyDecisionEvent> = decision_events
+        .read()
+        .map(|event| (&event.ant_id, event))
+        .collect();
+    for event in move_events.read() {
+        let Some(decision) = decisions.get(&event.ant_id) else {
+            continue;
+        };
+        auditor.sent.insert(
+            event.ant_id.clone(),
+            SentMove {
+                turn: decision.decision.turn_number,
+                strategy: decision.decision.chosen,
+                from: decision.position,
+                path: event.path.clone(),
+                enemies: game_state
+                    .enemy_ants
+                    .values()
+                    .map(|enemy| enemy.position)
+                    .collect(),
+            },
+        );
+    }
+
+    let turn = game_state.turn_number;
+    if !game_state.connected || auditor.last_turn == Some(turn) {
+        return;
+    }
+    // A new round started
+    if auditor.last_turn.is_some_and(|last| turn < last) {
+        *auditor = ExecutionAuditor::default();
+    }
+    auditor.last_turn = Some(turn);
+
+    let finished: Vec<(String, SentMove)> = auditor
+        .sent
+        .iter()
+        .filter(|(_, sent)| sent.turn < turn)
+        .map(|(id, sent)| (id.clone(), sent.clone()))
+        .collect();
+
+    for (ant_id, sent) in finished {
+        auditor.sent.remove(&ant_id);
+
+        // Moves from an older turn cannot be compared with this turn's positions
+        if sent.turn + 1 != turn {
+            continue;
+        }
+        let Some(ant) = game_state.my_ants.get(&ant_id) else {
+            continue;
+        };
+
+        let outcome = ExecutionAuditor::classify(&sent, ant, &game_state);
+        auditor.stats.entry(sent.strategy).or_default().add(outcome);
+
+        match outcome {
+            ExecutionOutcome::Completed => {}
+            ExecutionOutcome::BlockedByEnemy(pos) => {
+                debug!(
+                    "Ant {} from {:?} blocked by enemy at {:?}",
+                    ant_id, sent.from, pos
+                );
+                influence.learn_obstacle(pos, turn + ENEMY_OBSTACLE_TURNS - 1);
+            }
+            ExecutionOutcome::Blocked(pos) => {
+                info!(
+                    "Ant {} from {:?} could not enter {:?}, avoiding it for {} turns",
+                    ant_id, sent.from, pos, UNKNOWN_OBSTACLE_TURNS
+                );
+                influence.learn_obstacle(pos, turn + UNKNOWN_OBSTACLE_TURNS - 1);
+            }
+            ExecutionOutcome::BlockedByFriendly(_) | ExecutionOutcome::MpExhausted => {
+                debug!("Ant {} path outcome: {:?}", ant_id, outcome);
+            }
+        }
+    }
+
+    for ant in game_state.my_ants.values() {
+        if let Some(target) = ant.last_attack {
+            info!(
+                "Ant {} ({:?}) attacked {} at {:?}",
+                ant.id,
+                ant.ant_type,
+                ant.last_enemy_ant.as_deref().unwrap_or("an enemy"),
+                target
+            );
+        }
+    }
+}
@@ -0,0 +1,512 @@
+use crate::config::{AppConfig, CONFIG_PATH, CameraBookmark};
+use crate::input::CameraController;
+use crate::menu::MenuState;
+use crate::selection::Selection;
+use crate::types::*;
+use bevy::input::mouse::{MouseMotion, MouseWheel};
+use bevy::prelude::*;
+use bevy::render::camera::ScalingMode;
+use bevy_egui::{EguiContexts, egui};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const TRANSITION_SECONDS: f32 = 0.6;
+// How close to the window border the cursor pans the top-down camera, in pixels
+const EDGE_PAN_MARGIN: f32 = 16.0;
+const MIN_PITCH: f32 = 0.25;
+const MAX_PITCH: f32 = 1.45;
+// Q and E orbit speed, in radians per second
+const ORBIT_KEY_SPEED: f32 = 1.5;
+const LERP_SPEED: f32 = 10.0;
+
+const BOOKMARK_KEYS: [KeyCode; 9] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CameraMode {
+    #[default]
+    Free, // WASD and drag, looking straight down
+    Orbit,   // Circles a focus point
+    TopDown, // Orthographic, with edge pan and zoom to cursor
+    Follow,  // Orbits whatever is being followed
+}
+
+impl CameraMode {
+    pub const ALL: [CameraMode; 4] = [
+        CameraMode::Free,
+        CameraMode::Orbit,
+        CameraMode::TopDown,
+        CameraMode::Follow,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CameraMode::Free => "Free",
+            CameraMode::Orbit => "Orbit",
+            CameraMode::TopDown => "Top-Down",
+            CameraMode::Follow => "Follow",
+        }
+    }
+
+    fn next(&self) -> Self {
+        match self {
+            CameraMode::Free => CameraMode::Orbit,
+            CameraMode::Orbit => CameraMode::TopDown,
+            CameraMode::TopDown => CameraMode::Follow,
+            CameraMode::Follow => CameraMode::Free,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FollowTarget {
+    Unit(Entity), // One of our ants or an enemy, enemy entities keep their track across turns
+    Anthill,
+}
+
+struct CameraTransition {
+    from: Option<Transform>, // Filled in by the rig on the first frame
+    elapsed: f32,
+}
+
+#[derive(Resource)]
+pub struct CameraRig {
+    pub mode: CameraMode,
+    pub follow: FollowTarget,
+    pub yaw: f32,
+    pub pitch: f32, // Angle above the ground, orbit and follow only
+    focus: Vec3,    // Smoothed towards CameraController::target_position
+    transition: Option<CameraTransition>,
+    perspective: PerspectiveProjection, // Put back when leaving top-down
+}
+
+impl Default for CameraRig {
+    fn default() -> Self {
+        Self {
+            mode: CameraMode::Free,
+            follow: FollowTarget::Anthill,
+            yaw: 0.0,
+            pitch: 0.9,
+            focus: Vec3::ZERO,
+            transition: None,
+            perspective: PerspectiveProjection::default(),
+        }
+    }
+}
+
+impl CameraRig {
+    // Follow mode tracks the first selected unit, or the anthill when nothing is selected
+    pub fn set_mode(&mut self, mode: CameraMode, selection: &Selection) {
+        if mode == CameraMode::Follow {
+            self.follow = selection
+                .ants
+                .first()
+                .map_or(FollowTarget::Anthill, |entity| FollowTarget::Unit(*entity));
+        }
+        if mode != self.mode {
+            self.mode = mode;
+            self.start_transition();
+        }
+    }
+
+    fn start_transition(&mut self) {
+        self.transition = Some(CameraTransition {
+            from: None,
+            elapsed: 0.0,
+        });
+    }
+
+    // Where the camera should be in the current mode
+    fn pose(&self, controller: &CameraController) -> Transform {
+        match self.mode {
+            CameraMode::Free | CameraMode::TopDown => {
+                let position = Vec3::new(self.focus.x, controller.current_zoom, self.focus.z);
+                Transform::from_translation(position)
+                    .looking_at(Vec3::new(self.focus.x, 0.0, self.focus.z), Vec3::Y)
+            }
+            CameraMode::Orbit | CameraMode::Follow => {
+                let offset = Vec3::new(
+                    self.yaw.sin() * self.pitch.cos(),
+                    self.pitch.sin(),
+                    self.yaw.cos() * self.pitch.cos(),
+                );
+                Transform::from_translation(self.focus + offset * controller.current_zoom)
+                    .looking_at(self.focus, Vec3::Y)
+            }
+        }
+    }
+
+    fn recall(&mut self, bookmark: &CameraBookmark, controller: &mut CameraController) {
+        self.mode = match bookmark.mode {
+            CameraMode::Follow => CameraMode::Orbit,
+            mode => mode,
+        };
+        self.yaw = bookmark.yaw;
+        self.pitch = bookmark.pitch.clamp(MIN_PITCH, MAX_PITCH);
+        controller.target_position = Vec3::new(bookmark.focus.0, 0.0, bookmark.focus.1);
+        controller.target_zoom = bookmark
+            .zoom
+            .clamp(controller.min_zoom, controller.max_zoom);
+        // Fly there even if the mode did not change
+        self.start_transition();
+    }
+
+    fn bookmark(&self, slot: u8, controller: &CameraController) -> CameraBookmark {
+        CameraBookmark {
+            slot,
+            // Followed units are gone next round, so keep only the view
+            mode: match self.mode {
+                CameraMode::Follow => CameraMode::Orbit,
+                mode => mode,
+            },
+            focus: (controller.target_position.x, controller.target_position.z),
+            zoom: controller.target_zoom,
+            yaw: self.yaw,
+            pitch: self.pitch,
+        }
+    }
+}
+
+fn save_bookmark(
+    slot: u8,
+    rig: &CameraRig,
+    controller: &CameraController,
+    app_config: &mut ResMut<AppConfig>,
+) {
+    // Saving a bookmark must not make sync_camera_settings reset the zoom
+    let config = app_config.bypass_change_detection();
+    config
+        .camera
+        .bookmarks
+        .retain(|bookmark| bookmark.slot != slot);
+    config.camera.bookmarks.push(rig.bookmark(slot, controller));
+    config
+        .camera
+        .bookmarks
+        .sort_by_key(|bookmark| bookmark.slot);
+
+    match config.save(Path::new(CONFIG_PATH)) {
+        Ok(()) => info!("Camera bookmark {} saved", slot),
+        Err(e) => warn!("Failed to save camera bookmark {}: {}", slot, e),
+    }
+}
+
+fn ground_point(ray: Ray3d) -> Option<Vec3> {
+    ray.intersect_plane(Vec3::ZERO, InfinitePlane3d::new(Vec3::Y))
+        .map(|distance| ray.get_point(distance))
+}
+
+fn smoothstep(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+pub fn camera_mode_input_system(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    menu_state: Res<MenuState>,
+    selection: Res<Selection>,
+    mut rig: ResMut<CameraRig>,
+    mut controller: ResMut<CameraController>,
+    mut app_config: ResMut<AppConfig>,
+) {
+    if menu_state.show_menu {
+        return;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::KeyV) {
+        let mode = rig.mode.next();
+        rig.set_mode(mode, &selection);
+        info!("Camera mode: {}", mode.label());
+    }
+
+    let ctrl = keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    for (slot, key) in (1..).zip(BOOKMARK_KEYS) {
+        if !keyboard_input.just_pressed(key) {
+            continue;
+        }
+        if ctrl {
+            save_bookmark(slot, &rig, &controller, &mut app_config);
+        } else if let Some(bookmark) = app_config
+            .camera
+            .bookmarks
+            .iter()
+            .find(|bookmark| bookmark.slot == slot)
+        {
+            rig.recall(bookmark, &mut controller);
+        }
+    }
+}
+
+// Drives every mode but free, which camera_movement_system handles, and blends between modes
+pub fn camera_rig_system(
+    mut contexts: EguiContexts,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    mut mouse_motion_events: EventReader<MouseMotion>,
+    mut scroll_events: EventReader<MouseWheel>,
+    mut cameras: Query<
+        (&mut Transform, &mut Projection, &Camera, &GlobalTransform),
+        With<GameCamera>,
+    >,
+    units: Query<&GlobalTransform, (With<AntMarker>, Without<GameCamera>)>,
+    windows: Query<&Window>,
+    mut controller: ResMut<CameraController>,
+    mut rig: ResMut<CameraRig>,
+    menu_state: Res<MenuState>,
+    game_state: Res<GameState>,
+    time: Res<Time>,
+) -> Result {
+    let Ok((mut transform, mut projection, camera, camera_transform)) = cameras.single_mut() else {
+        return Ok(());
+    };
+    let rig = &mut *rig;
+    let dt = time.delta_secs();
+
+    if let Some(transition) = rig
+        .transition
+        .as_mut()
+        .filter(|transition| transition.from.is_none())
+    {
+        transition.from = Some(*transform);
+        // Start from the point the camera is looking at
+        rig.focus = ground_point(Ray3d::new(transform.translation, transform.forward()))
+            .unwrap_or(transform.translation.with_y(0.0));
+    }
+
+    // Leaving top-down: the view matches the orthographic one at this height
+    if rig.mode != CameraMode::TopDown && matches!(*projection, Projection::Orthographic(_)) {
+        *projection = Projection::Perspective(rig.perspective.clone());
+    }
+
+    let ctx = contexts.ctx_mut()?;
+    let input_allowed = !menu_state.show_menu && rig.mode != CameraMode::Free;
+    let pointer_free = !ctx.is_pointer_over_area();
+    let speed = if keyboard_input.pressed(KeyCode::ShiftLeft) {
+        controller.movement_speed * controller.sprint_multiplier
+    } else {
+        controller.movement_speed
+    };
+    let scroll: f32 = scroll_events.read().map(|event| event.y).sum();
+    let motion: Vec2 = mouse_motion_events.read().map(|event| event.delta).sum();
+
+    if input_allowed {
+        // Keys and pans move along the ground as seen on screen
+        let right = transform.right().with_y(0.0).normalize_or_zero();
+        let screen_up = if rig.mode == CameraMode::TopDown {
+            transform.up().with_y(0.0).normalize_or_zero()
+        } else {
+            transform.forward().with_y(0.0).normalize_or_zero()
+        };
+        let mut movement = Vec2::ZERO;
+        if keyboard_input.pressed(KeyCode::KeyW) {
+            movement.y += 1.0;
+        }
+        if keyboard_input.pressed(KeyCode::KeyS) {
+            movement.y -= 1.0;
+        }
+        if keyboard_input.pressed(KeyCode::KeyA) {
+            movement.x -= 1.0;
+        }
+        if keyboard_input.pressed(KeyCode::KeyD) {
+            movement.x += 1.0;
+        }
+
+        match rig.mode {
+            CameraMode::Orbit | CameraMode::Follow => {
+                if keyboard_input.pressed(KeyCode::KeyQ) {
+                    rig.yaw -= ORBIT_KEY_SPEED * dt;
+                }
+                if keyboard_input.pressed(KeyCode::KeyE) {
+                    rig.yaw += ORBIT_KEY_SPEED * dt;
+                }
+                if mouse_button_input.pressed(MouseButton::Right) {
+                    let turn = motion * controller.mouse_sensitivity * 0.01;
+                    rig.yaw -= turn.x;
+                    rig.pitch = (rig.pitch + turn.y).clamp(MIN_PITCH, MAX_PITCH);
+                }
+            }
+            CameraMode::TopDown => {
+                let cursor = windows
+                    .single()
+                    .ok()
+                    .filter(|_| pointer_free)
+                    .and_then(|window| Some((window.cursor_position()?, window.size())));
+                if let Some((cursor, size)) = cursor {
+                    if cursor.x < EDGE_PAN_MARGIN {
+                        movement.x -= 1.0;
+                    } else if cursor.x > size.x - EDGE_PAN_MARGIN {
+                        movement.x += 1.0;
+                    }
+                    if cursor.y < EDGE_PAN_MARGIN {
+                        movement.y += 1.0;
+                    } else if cursor.y > size.y - EDGE_PAN_MARGIN {
+                        movement.y -= 1.0;
+                    }
+                }
+            }
+            CameraMode::Free => {}
+        }
+
+        // Followed units decide where the camera goes
+        if rig.mode != CameraMode::Follow {
+            // Pan faster when zoomed out, as camera_movement_system does with drags
+            let zoom_factor = controller.current_zoom / 20.0;
+            let step = (right * movement.x + screen_up * movement.y) * speed * zoom_factor * dt;
+            controller.target_position += step;
+        }
+
+        if scroll != 0.0 && pointer_free {
+            let old_zoom = controller.target_zoom;
+            controller.target_zoom = (old_zoom - scroll * controller.zoom_speed * 0.15)
+                .clamp(controller.min_zoom, controller.max_zoom);
+
+            // Keep the hex under the cursor in place
+            if rig.mode == CameraMode::TopDown {
+                let cursor_ground = windows
+                    .single()
+                    .ok()
+                    .and_then(|window| window.cursor_position())
+                    .and_then(|cursor| camera.viewport_to_world(camera_transform, cursor).ok())
+                    .and_then(ground_point);
+                if let Some(point) = cursor_ground {
+                    let ratio = controller.target_zoom / old_zoom;
+                    controller.target_position =
+                        point + (controller.target_position - point) * ratio;
+                }
+            }
+        }
+    }
+
+    if rig.mode == CameraMode::Follow {
+        let followed = match rig.follow {
+            FollowTarget::Unit(entity) => units.get(entity).ok().map(|unit| unit.translation()),
+            FollowTarget::Anthill => Some(hex_to_world_pos(&game_state.main_spot)),
+        };
+        match followed {
+            Some(position) => controller.target_position = position.with_y(0.0),
+            // Dead or out of sight, watch the anthill instead
+            None => rig.follow = FollowTarget::Anthill,
+        }
+    }
+
+    if rig.mode == CameraMode::Free && rig.transition.is_none() {
+        return Ok(());
+    }
+
+    if rig.mode != CameraMode::Free {
+        let t = (LERP_SPEED * dt).min(1.0);
+        rig.focus = rig.focus.lerp(controller.target_position.with_y(0.0), t);
+        controller.current_zoom = controller.current_zoom.lerp(controller.target_zoom, t);
+    } else {
+        // camera_movement_system moves the camera itself, blend towards where it is heading
+        rig.focus = controller.target_position.with_y(0.0);
+    }
+    let pose = rig.pose(&controller);
+
+    match rig.transition.as_mut() {
+        Some(transition) => {
+            transition.elapsed += dt;
+            let t = smoothstep(transition.elapsed / TRANSITION_SECONDS);
+            let from = transition.from.unwrap_or(pose);
+            transform.translation = from.translation.lerp(pose.translation, t);
+            transform.rotation = from.rotation.slerp(pose.rotation, t);
+            if t >= 1.0 {
+                rig.transition = None;
+            }
+        }
+        None => *transform = pose,
+    }
+
+    // Switch to orthographic once the camera looks straight down, sized to match the view
+    if rig.mode == CameraMode::TopDown && rig.transition.is_none() {
+        if let Projection::Perspective(perspective) = &*projection {
+            rig.perspective = perspective.clone();
+        }
+        let viewport_height = 2.0 * controller.current_zoom * (rig.perspective.fov / 2.0).tan();
+        match projection.as_mut() {
+            Projection::Orthographic(orthographic) => {
+                orthographic.scaling_mode = ScalingMode::FixedVertical { viewport_height };
+            }
+            _ => {
+                *projection = Projection::Orthographic(OrthographicProjection {
+                    scaling_mode: ScalingMode::FixedVertical { viewport_height },
+                    ..OrthographicProjection::default_3d()
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn camera_toolbar_system(
+    mut contexts: EguiContexts,
+    mut rig: ResMut<CameraRig>,
+    mut controller: ResMut<CameraController>,
+    app_config: Res<AppConfig>,
+    selection: Res<Selection>,
+    markers: Query<&AntMarker>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    egui::Area::new(egui::Id::new("camera_toolbar"))
+        .anchor(egui::Align2::CENTER_BOTTOM, egui::vec2(0.0, -8.0))
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Camera (V):");
+                    for mode in CameraMode::ALL {
+                        if ui
+                            .selectable_label(rig.mode == mode, mode.label())
+                            .clicked()
+                        {
+                            rig.set_mode(mode, &selection);
+                        }
+                    }
+
+                    if rig.mode == CameraMode::Follow {
+                        let followed = match rig.follow {
+                            FollowTarget::Unit(entity) => match markers.get(entity) {
+                                Ok(marker) if marker.is_enemy => {
+                                    format!("enemy {:?}", marker.ant_type)
+                                }
+                                Ok(marker) => format!("{:?} {}", marker.ant_type, marker.ant_id),
+                                Err(_) => "nothing".to_string(),
+                            },
+                            FollowTarget::Anthill => "anthill".to_string(),
+                        };
+                        ui.separator();
+                        ui.label(format!("Following {}", followed));
+                    }
+
+                    if !app_config.camera.bookmarks.is_empty() {
+                        ui.separator();
+                        for bookmark in &app_config.camera.bookmarks {
+                            let button =
+                                ui.small_button(bookmark.slot.to_string())
+                                    .on_hover_text(format!(
+                                        "{} view, press {} to fly here and Ctrl+{} to overwrite",
+                                        bookmark.mode.label(),
+                                        bookmark.slot,
+                                        bookmark.slot
+                                    ));
+                            if button.clicked() {
+                                rig.recall(bookmark, &mut controller);
+                            }
+                        }
+                    }
+                });
+            });
+        });
+
+    Ok(())
+}
@@ -0,0 +1,250 @@
+use crate::influence::InfluenceMap;
+use crate::types::*;
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+// Colony-wide rules applied on top of whatever the strategies planned
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnOutcome {
+    Spawned(AntType),
+    Missed,
+    Capped, // Colony is at MAX_ANTS, nothing could spawn
+}
+
+// Compares what the main anthill hex actually produced with the 10/30/60% odds
+#[derive(Resource, Default)]
+pub struct SpawnTracker {
+    last_turn: Option<i32>,
+    known_ants: HashSet<String>,
+    spawned: HashMap<AntType, u32>,
+    missed: u32,
+    capped_turns: u32,
+    // Sum of the per-turn chance that the rolled type was already standing on the spot
+    expected_misses: f32,
+}
+
+impl SpawnTracker {
+    pub fn spawned(&self, ant_type: AntType) -> u32 {
+        self.spawned.get(&ant_type).copied().unwrap_or(0)
+    }
+
+    pub fn total_spawned(&self) -> u32 {
+        self.spawned.values().sum()
+    }
+
+    pub fn missed(&self) -> u32 {
+        self.missed
+    }
+
+    pub fn expected_misses(&self) -> f32 {
+        self.expected_misses
+    }
+
+    pub fn capped_turns(&self) -> u32 {
+        self.capped_turns
+    }
+
+    // Share of spawned units of this type, to hold against AntType::spawn_chance
+    pub fn observed_share(&self, ant_type: AntType) -> f32 {
+        let total = self.total_spawned();
+        if total == 0 {
+            return 0.0;
+        }
+        self.spawned(ant_type) as f32 / total as f32
+    }
+
+    pub fn observe(&mut self, game_state: &GameState) -> Option<SpawnOutcome> {
+        let turn = game_state.turn_number;
+        let current: HashSet<String> = game_state.my_ants.keys().cloned().collect();
+
+        let Some(last_turn) = self.last_turn else {
+            self.last_turn = Some(turn);
+            self.known_ants = current;
+            return None;
+        };
+
+        if turn == last_turn {
+            return None;
+        }
+
+        // A new round started
+        if turn < last_turn {
+            *self = Self::default();
+            self.last_turn = Some(turn);
+            self.known_ants = current;
+            return None;
+        }
+
+        let new_ants: Vec<&Ant> = game_state
+            .my_ants
+            .values()
+            .filter(|ant| !self.known_ants.contains(&ant.id))
+            .collect();
+
+        // Types already standing on the spot would have made a roll of that type fail
+        let blockers: HashSet<AntType> = game_state
+            .my_ants
+            .values()
+            .filter(|ant| ant.position == game_state.main_spot)
+            .filter(|ant| !new_ants.iter().any(|new| new.id == ant.id))
+            .map(|ant| ant.ant_type)
+            .collect();
+
+        let consecutive = turn == last_turn + 1;
+        let at_cap = game_state.my_ants.len() as i32 >= MAX_ANTS;
+
+        self.last_turn = Some(turn);
+        self.known_ants = current;
+
+        if consecutive && !at_cap {
+            self.expected_misses += blockers.iter().map(|t| t.spawn_chance()).sum::<f32>();
+        }
+
+        if let Some(ant) = new_ants.first() {
+            for ant in &new_ants {
+                *self.spawned.entry(ant.ant_type).or_insert(0) += 1;
+            }
+            return Some(SpawnOutcome::Spawned(ant.ant_type));
+        }
+
+        // With skipped polls we cannot tell which turn should have spawned
+        if !consecutive {
+            return None;
+        }
+
+        if at_cap {
+            self.capped_turns += 1;
+            Some(SpawnOutcome::Capped)
+        } else {
+            self.missed += 1;
+            Some(SpawnOutcome::Missed)
+        }
+    }
+}
+
+pub fn track_spawns(game_state: Res<GameState>, mut tracker: ResMut<SpawnTracker>) {
+    if !game_state.is_changed() || !game_state.connected {
+        return;
+    }
+
+    match tracker.observe(&game_state) {
+        Some(SpawnOutcome::Spawned(ant_type)) => {
+            debug!("Turn #{}: spawned a {:?}", game_state.turn_number, ant_type);
+        }
+        Some(SpawnOutcome::Missed) => {
+            let on_spot: Vec<AntType> = game_state
+                .my_ants
+                .values()
+                .filter(|ant| ant.position == game_state.main_spot)
+                .map(|ant| ant.ant_type)
+                .collect();
+            warn!(
+                "Turn #{}: spawn skipped, main spot held by {:?} ({} misses so far, {:.1} expected)",
+                game_state.turn_number,
+                on_spot,
+                tracker.missed(),
+                tracker.expected_misses()
+            );
+        }
+        Some(SpawnOutcome::Capped) | None => {}
+    }
+}
+
+/// Keep the main anthill hex free at the end of every turn so unit creation is never skipped.
+/// Routes that would stop on the spot are cut short, and ants left standing on it are
+/// moved to the safest free neighbour. Ants under manual orders are never rerouted, `locked`
+/// maps them to where their manual leg ends this turn. Returns how many paths were changed.
+pub fn keep_spawn_slot_clear<'a>(
+    planned_moves: &mut HashMap<&'a String, Vec<HexCoord>>,
+    locked: &HashMap<&String, HexCoord>,
+    game_state: &'a GameState,
+    influence: &InfluenceMap,
+) -> usize {
+    let spot = game_state.main_spot;
+    let mut changed = 0;
+
+    // Passing through the spot is fine, stopping on it is not
+    for path in planned_moves.values_mut() {
+        let before = path.len();
+        while path.last() == Some(&spot) {
+            path.pop();
+        }
+        if path.len() != before {
+            changed += 1;
+        }
+    }
+
+    // Where everyone will stand once this turn's moves are done
+    let end_position = |ant: &Ant, moves: &HashMap<&String, Vec<HexCoord>>| {
+        locked
+            .get(&ant.id)
+            .or_else(|| moves.get(&ant.id).and_then(|path| path.last()))
+            .copied()
+            .unwrap_or(ant.position)
+    };
+    let mut taken: HashSet<(AntType, HexCoord)> = game_state
+        .my_ants
+        .values()
+        .map(|ant| (ant.ant_type, end_position(ant, planned_moves)))
+        .collect();
+
+    let mut stuck: Vec<&Ant> = game_state
+        .my_ants
+        .values()
+        .filter(|ant| !locked.contains_key(&ant.id))
+        .filter(|ant| end_position(ant, planned_moves) == spot)
+        .collect();
+    stuck.sort_by(|a, b| a.id.cmp(&b.id));
+
+    for ant in stuck {
+        let exit = spot
+            .neighbors()
+            .into_iter()
+            .filter(|pos| {
+                game_state
+                    .visible_tiles
+                    .get(pos)
+                    .is_some_and(|tile| tile.tile_type.is_passable())
+            })
+            .filter(|pos| !taken.contains(&(ant.ant_type, *pos)))
+            .filter(|pos| {
+                !game_state
+                    .enemy_ants
+                    .values()
+                    .any(|enemy| enemy.position == *pos)
+            })
+            // Other anthill hexes first, then whatever hurts least
+            .min_by(|a, b| {
+                let key = |pos: &HexCoord| {
+                    (
+                        !game_state.home_tiles.contains(pos),
+                        influence.end_of_turn_damage(pos) + influence.enemy_threat(pos),
+                    )
+                };
+                let (a_away, a_cost) = key(a);
+                let (b_away, b_cost) = key(b);
+                a_away.cmp(&b_away).then(
+                    a_cost
+                        .partial_cmp(&b_cost)
+                        .unwrap_or(std::cmp::Ordering::Equal),
+                )
+            });
+
+        let Some(exit) = exit else {
+            warn!(
+                "Ant {} cannot leave the main spot, no free neighbour",
+                ant.id
+            );
+            continue;
+        };
+
+        debug!("Ant {} steps off the main spot to {:?}", ant.id, exit);
+        taken.remove(&(ant.ant_type, spot));
+        taken.insert((ant.ant_type, exit));
+        planned_moves.insert(&ant.id, vec![exit]);
+        changed += 1;
+    }
+
+    changed
+}
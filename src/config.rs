@@ -1,7 +1,13 @@
+use crate::camera_modes::CameraMode;
+use crate::types::AntType;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+pub const CONFIG_PATH: &str = "config.toml";
 
 #[derive(Debug, Clone, Serialize, Deserialize, Resource)]
 pub struct AppConfig {
@@ -10,6 +16,8 @@ pub struct AppConfig {
     pub camera: CameraConfig,
     pub ui: UiConfig,
     pub debug: DebugConfig,
+    #[serde(default)]
+    pub strategy: StrategyConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +27,13 @@ pub struct ServerConfig {
     pub tick_rate_ms: u64,
     pub auto_reconnect: bool,
     pub timeout_seconds: u64,
+    // How often /logs is polled, requests still go through the rate limiter
+    #[serde(default = "default_logs_poll_ms")]
+    pub logs_poll_ms: u64,
+}
+
+fn default_logs_poll_ms() -> u64 {
+    5000
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +46,19 @@ pub struct CameraConfig {
     pub max_zoom: f32,
     pub current_zoom: f32,
     pub drag_sensitivity: f32,
+    #[serde(default)]
+    pub bookmarks: Vec<CameraBookmark>,
+}
+
+// A saved camera view, Ctrl+number saves it and the number key flies back to it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CameraBookmark {
+    pub slot: u8,
+    pub mode: CameraMode,
+    pub focus: (f32, f32), // Ground point the camera looks at, world x and z
+    pub zoom: f32,
+    pub yaw: f32,
+    pub pitch: f32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -65,6 +93,247 @@ pub struct RendererConfig {
     pub clear_color: (f32, f32, f32),
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StrategyConfig {
+    pub commitment_turns: i32,
+    pub switch_hysteresis: f32,
+    pub low_health_ratio: f32,
+    // Ants retreat home when projected HP after this turn's exposure drops below this ratio
+    pub retreat_health_ratio: f32,
+    pub explore: ExploreWeights,
+    pub gather: GatherWeights,
+    pub defend: DefendWeights,
+    pub attack: AttackWeights,
+    // Strategy name -> ant types it may be used for. Unlisted strategies are always enabled
+    pub enabled: BTreeMap<String, AntTypeToggles>,
+}
+
+impl StrategyConfig {
+    pub fn is_enabled(&self, strategy: &str, ant_type: AntType) -> bool {
+        self.enabled
+            .get(strategy)
+            .is_none_or(|toggles| toggles.get(ant_type))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BasePriorities {
+    pub worker: f32,
+    pub soldier: f32,
+    pub scout: f32,
+}
+
+impl BasePriorities {
+    pub fn get(&self, ant_type: AntType) -> f32 {
+        match ant_type {
+            AntType::Worker => self.worker,
+            AntType::Soldier => self.soldier,
+            AntType::Scout => self.scout,
+        }
+    }
+
+    pub fn get_mut(&mut self, ant_type: AntType) -> &mut f32 {
+        match ant_type {
+            AntType::Worker => &mut self.worker,
+            AntType::Soldier => &mut self.soldier,
+            AntType::Scout => &mut self.scout,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AntTypeToggles {
+    pub worker: bool,
+    pub soldier: bool,
+    pub scout: bool,
+}
+
+impl Default for AntTypeToggles {
+    fn default() -> Self {
+        Self {
+            worker: true,
+            soldier: true,
+            scout: true,
+        }
+    }
+}
+
+impl AntTypeToggles {
+    pub fn get(&self, ant_type: AntType) -> bool {
+        match ant_type {
+            AntType::Worker => self.worker,
+            AntType::Soldier => self.soldier,
+            AntType::Scout => self.scout,
+        }
+    }
+
+    pub fn get_mut(&mut self, ant_type: AntType) -> &mut bool {
+        match ant_type {
+            AntType::Worker => &mut self.worker,
+            AntType::Soldier => &mut self.soldier,
+            AntType::Scout => &mut self.scout,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExploreWeights {
+    pub base: BasePriorities,
+    pub few_tiles_threshold: usize,
+    pub few_tiles_bonus: f32,
+    pub some_tiles_threshold: usize,
+    pub some_tiles_bonus: f32,
+    pub many_tiles_threshold: usize,
+    pub many_tiles_bonus: f32,
+    pub frontier_bonus: f32,
+    pub movement_bonus: f32,
+    pub base_proximity_bonus: f32,
+    pub base_proximity_falloff: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GatherWeights {
+    pub base: BasePriorities,
+    pub high_food_threshold: usize,
+    pub high_food_bonus: f32,
+    pub medium_food_threshold: usize,
+    pub medium_food_bonus: f32,
+    pub low_food_threshold: usize,
+    pub low_food_bonus: f32,
+    pub any_food_bonus: f32,
+    pub return_bonus: f32,
+    pub proximity_bonus: f32,
+    pub carried_food_weight: f32,
+    pub contested_food_divisor: f32,
+    pub return_fill_ratio: f32,
+    pub return_fill_per_ant_in_base: f32,
+    pub loot_weight: f32,
+    pub loot_radius: i32,
+    pub loot_bonus: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DefendWeights {
+    pub base: BasePriorities,
+    pub enemies_near_home_bonus: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AttackWeights {
+    pub base: BasePriorities,
+    pub min_soldiers: usize,
+    pub soldier_count_bonus: f32,
+    pub favorable_engagement_bonus: f32,
+    pub unfavorable_engagement_bonus: f32,
+    pub squad_size: usize,
+    pub assembly_radius: i32,
+    pub focus_fire_bonus: f32,
+}
+
+impl Default for StrategyConfig {
+    fn default() -> Self {
+        Self {
+            commitment_turns: 3,
+            switch_hysteresis: 2.5,
+            low_health_ratio: 0.3,
+            retreat_health_ratio: 0.25,
+            explore: ExploreWeights::default(),
+            gather: GatherWeights::default(),
+            defend: DefendWeights::default(),
+            attack: AttackWeights::default(),
+            enabled: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for ExploreWeights {
+    fn default() -> Self {
+        Self {
+            base: BasePriorities {
+                worker: 6.0,
+                soldier: 3.0,
+                scout: 8.0,
+            },
+            few_tiles_threshold: 30,
+            few_tiles_bonus: 7.0,
+            some_tiles_threshold: 80,
+            some_tiles_bonus: 4.0,
+            many_tiles_threshold: 150,
+            many_tiles_bonus: 2.0,
+            frontier_bonus: 4.0,
+            movement_bonus: 1.0,
+            base_proximity_bonus: 25.0,
+            base_proximity_falloff: 5.0,
+        }
+    }
+}
+
+impl Default for GatherWeights {
+    fn default() -> Self {
+        Self {
+            base: BasePriorities {
+                worker: 9.0,
+                soldier: 2.0,
+                scout: 4.0,
+            },
+            high_food_threshold: 40,
+            high_food_bonus: 7.5,
+            medium_food_threshold: 20,
+            medium_food_bonus: 5.0,
+            low_food_threshold: 10,
+            low_food_bonus: 3.0,
+            any_food_bonus: 1.0,
+            return_bonus: 20.0,
+            proximity_bonus: 20.0,
+            carried_food_weight: 5.0,
+            contested_food_divisor: 100.0,
+            return_fill_ratio: 0.5,
+            return_fill_per_ant_in_base: 0.12,
+            loot_weight: 2.0,
+            loot_radius: 8,
+            loot_bonus: 6.0,
+        }
+    }
+}
+
+impl Default for DefendWeights {
+    fn default() -> Self {
+        Self {
+            base: BasePriorities {
+                worker: 3.0,
+                soldier: 8.0,
+                scout: 2.0,
+            },
+            enemies_near_home_bonus: 10.0,
+        }
+    }
+}
+
+impl Default for AttackWeights {
+    fn default() -> Self {
+        Self {
+            base: BasePriorities {
+                worker: 1.0,
+                soldier: 7.0,
+                scout: 3.0,
+            },
+            min_soldiers: 3,
+            soldier_count_bonus: 5.0,
+            favorable_engagement_bonus: 3.0,
+            unfavorable_engagement_bonus: 1.0,
+            squad_size: 3,
+            assembly_radius: 2,
+            focus_fire_bonus: 6.0,
+        }
+    }
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
@@ -74,6 +343,7 @@ impl Default for AppConfig {
                 tick_rate_ms: 1000,
                 auto_reconnect: true,
                 timeout_seconds: 10,
+                logs_poll_ms: default_logs_poll_ms(),
             },
             renderer: RendererConfig {
                 target_fps: 60,
@@ -96,6 +366,7 @@ impl Default for AppConfig {
                 max_zoom: 50.0,
                 current_zoom: 20.0,
                 drag_sensitivity: 0.01,
+                bookmarks: Vec::new(),
             },
             ui: UiConfig {
                 show_fps: false,
@@ -111,6 +382,7 @@ impl Default for AppConfig {
                 debug_mode: false,
                 log_level: "info".to_string(),
             },
+            strategy: StrategyConfig::default(),
         }
     }
 }
@@ -137,3 +409,58 @@ impl AppConfig {
         Ok(())
     }
 }
+
+// Polls the config file so edits made outside the app take effect mid-round
+#[derive(Resource)]
+pub struct ConfigWatcher {
+    pub timer: Timer,
+    last_modified: Option<SystemTime>,
+}
+
+impl Default for ConfigWatcher {
+    fn default() -> Self {
+        Self {
+            timer: Timer::new(Duration::from_secs(1), TimerMode::Repeating),
+            last_modified: Self::modified_time(),
+        }
+    }
+}
+
+impl ConfigWatcher {
+    fn modified_time() -> Option<SystemTime> {
+        fs::metadata(CONFIG_PATH)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+}
+
+pub fn setup_config_watcher(mut commands: Commands) {
+    commands.insert_resource(ConfigWatcher::default());
+}
+
+pub fn watch_config_file(
+    time: Res<Time>,
+    mut watcher: ResMut<ConfigWatcher>,
+    mut app_config: ResMut<AppConfig>,
+) {
+    watcher.timer.tick(time.delta());
+    if !watcher.timer.just_finished() {
+        return;
+    }
+
+    let modified = ConfigWatcher::modified_time();
+    if modified.is_none() || modified == watcher.last_modified {
+        return;
+    }
+    watcher.last_modified = modified;
+
+    match AppConfig::load_or_create(Path::new(CONFIG_PATH)) {
+        Ok(config) => {
+            if config.strategy != app_config.strategy {
+                app_config.strategy = config.strategy;
+                info!("Strategy weights reloaded from {}", CONFIG_PATH);
+            }
+        }
+        Err(e) => warn!("Failed to reload {}: {}", CONFIG_PATH, e),
+    }
+}
@@ -0,0 +1,102 @@
+use crate::types::*;
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+// The anthill sees this far around the main spot
+pub const ANTHILL_VIEW_RANGE: i32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HexVisibility {
+    Visible,
+    Remembered, // Seen earlier this round, drawn as it looked back then
+    NeverSeen,
+}
+
+// What we have seen of the map this round
+#[derive(Resource, Default)]
+pub struct FogOfWar {
+    vision: HashSet<HexCoord>,
+    seen: HashMap<HexCoord, TileType>,
+    // Food piles as they were when their hex was last in sight
+    remembered_food: HashMap<HexCoord, FoodOnMap>,
+    last_turn: Option<i32>,
+}
+
+impl FogOfWar {
+    pub fn visibility(&self, pos: &HexCoord) -> HexVisibility {
+        if self.vision.contains(pos) {
+            HexVisibility::Visible
+        } else if self.seen.contains_key(pos) {
+            HexVisibility::Remembered
+        } else {
+            HexVisibility::NeverSeen
+        }
+    }
+
+    pub fn vision(&self) -> &HashSet<HexCoord> {
+        &self.vision
+    }
+
+    pub fn remembered_tile(&self, pos: &HexCoord) -> Option<TileType> {
+        self.seen.get(pos).copied()
+    }
+
+    // Every tile seen this round, as it looked last time
+    pub fn seen_tiles(&self) -> impl Iterator<Item = (&HexCoord, &TileType)> {
+        self.seen.iter()
+    }
+
+    // Food we saw earlier on hexes that are out of sight now
+    pub fn ghost_food(&self) -> impl Iterator<Item = (&HexCoord, &FoodOnMap)> {
+        self.remembered_food
+            .iter()
+            .filter(|(pos, _)| !self.vision.contains(pos))
+    }
+
+    fn hexes_within(center: HexCoord, range: i32) -> impl Iterator<Item = HexCoord> {
+        (-range..=range).flat_map(move |dq| {
+            let min_dr = (-range).max(-dq - range);
+            let max_dr = range.min(-dq + range);
+            (min_dr..=max_dr).map(move |dr| HexCoord::new(center.q + dq, center.r + dr))
+        })
+    }
+
+    pub fn update(&mut self, game_state: &GameState) {
+        // A new round may be played on a different map
+        if self
+            .last_turn
+            .is_some_and(|last| game_state.turn_number < last)
+        {
+            *self = Self::default();
+        }
+        self.last_turn = Some(game_state.turn_number);
+
+        let mut vision: HashSet<HexCoord> = game_state
+            .my_ants
+            .values()
+            .flat_map(|ant| Self::hexes_within(ant.position, ant.ant_type.view_range()))
+            .collect();
+        if !game_state.home_tiles.is_empty() {
+            vision.extend(Self::hexes_within(game_state.main_spot, ANTHILL_VIEW_RANGE));
+        }
+        self.vision = vision;
+
+        for (pos, tile) in &game_state.visible_tiles {
+            self.seen.insert(*pos, tile.tile_type);
+        }
+
+        // Forget food that is no longer where we can see, remember what is there now
+        let vision = &self.vision;
+        self.remembered_food
+            .retain(|pos, _| !vision.contains(pos) && !game_state.visible_tiles.contains_key(pos));
+        for (pos, food) in &game_state.food_on_map {
+            self.remembered_food.insert(*pos, food.clone());
+        }
+    }
+}
+
+pub fn update_fog_of_war(game_state: Res<GameState>, mut fog: ResMut<FogOfWar>) {
+    if game_state.is_changed() {
+        fog.update(&game_state);
+    }
+}
@@ -1,5 +1,11 @@
-use crate::strategy::StrategyManager;
+use crate::colony::keep_spawn_slot_clear;
+use crate::config::AppConfig;
+use crate::influence::InfluenceMap;
+use crate::manual_orders::ManualOrders;
+use crate::route::Route;
+use crate::strategy::{PlanGoal, StrategyDecision, StrategyManager, StrategyRegistry};
 use crate::types::*;
+use crate::utils::MovementManager;
 use bevy::prelude::*;
 use std::collections::HashMap;
 use std::time::Duration;
@@ -32,16 +38,37 @@ impl Default for GameLogic {
     }
 }
 
-pub fn setup_game_logic(mut commands: Commands) {
+pub fn setup_game_logic(
+    mut commands: Commands,
+    app_config: Res<AppConfig>,
+    registry: Res<StrategyRegistry>,
+) {
     commands.insert_resource(GameLogic::default());
-    commands.insert_resource(StrategyManager::default());
+    commands.insert_resource(StrategyManager::from_registry(
+        &registry,
+        &app_config.strategy,
+    ));
+}
+
+pub fn sync_strategy_config(
+    app_config: Res<AppConfig>,
+    mut strategy_manager: ResMut<StrategyManager>,
+) {
+    // Only sync if config has changed
+    if app_config.is_changed() && !app_config.is_added() {
+        strategy_manager.apply_config(&app_config.strategy);
+    }
 }
 
 pub fn game_logic_system(
     mut game_logic: ResMut<GameLogic>,
     game_state: Res<GameState>,
+    influence: Res<InfluenceMap>,
+    manual_orders: Res<ManualOrders>,
     mut strategy_manager: ResMut<StrategyManager>,
     mut move_events: EventWriter<MoveCommandEvent>,
+    mut plan_events: EventWriter<PlanInvalidatedEvent>,
+    mut decision_events: EventWriter<StrategyDecisionEvent>,
     _time: Res<Time>,
 ) {
     use std::collections::{HashMap, HashSet};
@@ -60,24 +87,144 @@ pub fn game_logic_system(
 
     info!("Turn #{}: Strategy assignments:", game_state.turn_number);
 
-    // Step 1: Collect all planned moves
+    // Step 1: Advance per-ant plan memory and drop plans that no longer hold
+    for event in strategy_manager.update_plans(&game_state, &influence) {
+        info!(
+            "Ant {} dropped '{}' plan: {:?}",
+            event.ant_id, event.strategy, event.reason
+        );
+        plan_events.write(event);
+    }
+
+    strategy_manager.prepare_turn(&game_state, &influence);
+
+    // Step 2: Collect all planned moves
     let mut planned_moves: HashMap<&String, Vec<HexCoord>> = HashMap::new();
     let mut strategy_names: HashMap<&String, &str> = HashMap::new();
+    let mut plans: Vec<(&Ant, &'static str, Option<PlanGoal>, Route)> = Vec::new();
+    let mut decisions: Vec<(&String, StrategyDecision)> = Vec::new();
 
     for (ant_id, ant) in &game_state.my_ants {
-        let best_strategy = strategy_manager.select_strategy(ant, &game_state);
-        let path = best_strategy.execute(ant, &game_state);
+        // Ants under manual orders are moved by dispatch_manual_orders
+        if manual_orders.is_locked(ant_id) {
+            debug!("Ant {} is under manual control, skipping", ant_id);
+            continue;
+        }
+
+        let Some((best_strategy, decision)) =
+            strategy_manager.select_strategy(ant, &game_state, &influence)
+        else {
+            debug!("Ant {} has no enabled strategy, idling", ant_id);
+            continue;
+        };
+        let name = best_strategy.name();
+
+        for score in &decision.scores {
+            debug!(
+                "Ant {} '{}': base {:.1} + global {:.1} + individual {:.1} + hysteresis {:.1} = {:.1}",
+                ant_id,
+                score.strategy,
+                score.base,
+                score.global,
+                score.individual,
+                score.hysteresis,
+                score.total
+            );
+        }
+
+        // Keep walking the remaining legs of a committed route instead of re-planning
+        let (path, goal, route) = match strategy_manager.reusable_plan(ant, name) {
+            Some(plan) => (plan.route.next_leg(), plan.goal, plan.route.clone()),
+            None => {
+                let goal = best_strategy.goal(ant, &game_state, &influence);
+                let route = goal
+                    .map(|goal| {
+                        MovementManager::plan_route(ant, goal.position(), &game_state, &influence)
+                    })
+                    .unwrap_or_default();
+                (route.next_leg(), goal, route)
+            }
+        };
+
+        // HP budget: pick a stop hex that hurts least, pull back home if even that is too much
+        let path = MovementManager::hazard_aware_stop(ant, path, &game_state, &influence);
+        let stop = path.last().copied().unwrap_or(ant.position);
+        let retreat_health = strategy_manager.retreat_health_ratio() * ant.max_health.max(1) as f32;
+        let projected_health = MovementManager::projected_health(ant, stop, &influence);
+        let (path, goal, route) = match MovementManager::nearest_home(ant, &game_state) {
+            Some(home)
+                if projected_health < retreat_health
+                    && !game_state.home_tiles.contains(&ant.position)
+                    && !matches!(goal, Some(PlanGoal::Home(_))) =>
+            {
+                info!(
+                    "Ant {} retreats home, projected HP {:.0} below {:.0}",
+                    ant_id, projected_health, retreat_health
+                );
+                let route = MovementManager::plan_route(ant, home, &game_state, &influence);
+                let path = MovementManager::hazard_aware_stop(
+                    ant,
+                    route.next_leg(),
+                    &game_state,
+                    &influence,
+                );
+                (path, Some(PlanGoal::Home(home)), route)
+            }
+            _ => (path, goal, route),
+        };
+
         planned_moves.insert(ant_id, path);
-        strategy_names.insert(ant_id, best_strategy.name());
+        decisions.push((ant_id, decision));
+        strategy_names.insert(ant_id, name);
+        plans.push((ant, name, goal, route));
+    }
+
+    // Step 3: Colony rules that override individual plans
+    let locked: HashMap<&String, HexCoord> = game_state
+        .my_ants
+        .iter()
+        .filter_map(|(ant_id, ant)| manual_orders.leg_end(ant).map(|end| (ant_id, end)))
+        .collect();
+    let strategy_paths = planned_moves.clone();
+    let rerouted = keep_spawn_slot_clear(&mut planned_moves, &locked, &game_state, &influence);
+    if rerouted > 0 {
+        debug!("Spawn slot rule changed {} paths", rerouted);
+    }
+
+    // A route the colony rules cut or replaced no longer matches what is sent, keep the goal
+    // and let the ant plan a fresh route from wherever it ends up
+    for (ant, name, goal, route) in plans {
+        let route = if planned_moves.get(&ant.id) == strategy_paths.get(&ant.id) {
+            route
+        } else {
+            Route::default()
+        };
+        strategy_manager.commit_plan(ant, name, goal, route, game_state.turn_number);
+    }
+
+    // Record the paths that will actually be sent, after the colony rules had their say
+    for (ant_id, decision) in decisions {
+        let ant = &game_state.my_ants[ant_id];
+        decision_events.write(StrategyDecisionEvent {
+            ant_id: ant_id.clone(),
+            ant_type: ant.ant_type,
+            position: ant.position,
+            decision: decision.clone(),
+            path: planned_moves.get(ant_id).cloned().unwrap_or_default(),
+        });
+        strategy_manager.record_decision(ant_id, decision);
     }
 
-    // Step 2: Reservation table to avoid move conflicts
+    // Step 4: Reservation table to avoid move conflicts
     let mut reserved: HashSet<HexCoord> = HashSet::new();
     for (ant_id, path) in planned_moves {
         let strategy_name = strategy_names.get(ant_id).unwrap_or(&"Unknown");
+        let eta = strategy_manager
+            .eta(ant_id)
+            .map_or(String::new(), |turns| format!(", ETA {} turns", turns));
         info!(
-            "Ant {} (type: {:?}) assigned '{}' strategy, path: {:?}",
-            ant_id, game_state.my_ants[ant_id].ant_type, strategy_name, path
+            "Ant {} (type: {:?}) assigned '{}' strategy, path: {:?}{}",
+            ant_id, game_state.my_ants[ant_id].ant_type, strategy_name, path, eta
         );
 
         // If the path is not empty, send a move command
@@ -0,0 +1,326 @@
+use crate::menu::MenuState;
+use crate::types::*;
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+use std::collections::{BTreeMap, HashSet, VecDeque};
+
+// Oldest records are dropped past this
+const MAX_RECORDS: usize = 2000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameLogKind {
+    MoveFailed,
+    Attack,
+    Death,
+    Theft,
+    Delivery,
+    Other,
+}
+
+impl GameLogKind {
+    pub const ALL: [GameLogKind; 6] = [
+        GameLogKind::MoveFailed,
+        GameLogKind::Attack,
+        GameLogKind::Death,
+        GameLogKind::Theft,
+        GameLogKind::Delivery,
+        GameLogKind::Other,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GameLogKind::MoveFailed => "Move failed",
+            GameLogKind::Attack => "Attack",
+            GameLogKind::Death => "Death",
+            GameLogKind::Theft => "Theft",
+            GameLogKind::Delivery => "Delivery",
+            GameLogKind::Other => "Other",
+        }
+    }
+
+    fn color(&self) -> egui::Color32 {
+        match self {
+            GameLogKind::MoveFailed => egui::Color32::YELLOW,
+            GameLogKind::Attack => egui::Color32::LIGHT_RED,
+            GameLogKind::Death => egui::Color32::RED,
+            GameLogKind::Theft => egui::Color32::from_rgb(255, 140, 0),
+            GameLogKind::Delivery => egui::Color32::GREEN,
+            GameLogKind::Other => egui::Color32::GRAY,
+        }
+    }
+
+    // The server does not document its messages, so match on the words they use
+    fn classify(message: &str) -> Self {
+        let message = message.to_lowercase();
+        let has = |words: &[&str]| words.iter().any(|word| message.contains(word));
+
+        if has(&["stole", "steal", "theft", "robbed"]) {
+            GameLogKind::Theft
+        } else if has(&["died", "killed", "dead", "destroyed"]) {
+            GameLogKind::Death
+        } else if has(&["attack", "damage", "hit "]) {
+            GameLogKind::Attack
+        } else if has(&["deliver", "brought", "calories"]) {
+            GameLogKind::Delivery
+        } else if has(&["move", "path"]) && has(&["fail", "invalid", "cannot", "can't", "blocked"])
+        {
+            GameLogKind::MoveFailed
+        } else {
+            GameLogKind::Other
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GameLogRecord {
+    pub turn: i32, // Turn we were on when the message arrived
+    pub time: String,
+    pub message: String,
+    pub kind: GameLogKind,
+    pub ant_ids: Vec<String>,
+}
+
+impl GameLogRecord {
+    pub fn parse(log: &ApiLogMessage, turn: i32, known_ants: &HashSet<String>) -> Self {
+        let ant_ids = log
+            .message
+            .split(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+            .filter(|token| known_ants.contains(*token) || Self::looks_like_id(token))
+            .map(str::to_string)
+            .fold(Vec::new(), |mut ids, id| {
+                if !ids.contains(&id) {
+                    ids.push(id);
+                }
+                ids
+            });
+
+        Self {
+            turn,
+            time: log.time.clone(),
+            message: log.message.clone(),
+            kind: GameLogKind::classify(&log.message),
+            ant_ids,
+        }
+    }
+
+    // Unit ids are UUIDs
+    fn looks_like_id(token: &str) -> bool {
+        token.len() == 36
+            && token.chars().filter(|c| *c == '-').count() == 4
+            && token.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+    }
+}
+
+// Parsed /logs messages, deduplicated by time and text
+#[derive(Resource, Default)]
+pub struct GameLog {
+    records: VecDeque<GameLogRecord>,
+    // Keys of what the server returned last, independent of which records were evicted
+    seen: HashSet<(String, String)>,
+    // Ids of every ant we had this round, so messages about dead ants still link up
+    known_ants: HashSet<String>,
+}
+
+impl GameLog {
+    pub fn records(&self) -> impl Iterator<Item = &GameLogRecord> {
+        self.records.iter()
+    }
+
+    pub fn ingest(&mut self, logs: &[ApiLogMessage], turn: i32) -> usize {
+        let mut added = 0;
+        for log in logs {
+            if !self.seen.insert((log.time.clone(), log.message.clone())) {
+                continue;
+            }
+
+            let record = GameLogRecord::parse(log, turn, &self.known_ants);
+            debug!(
+                "Game log [{}] {:?}: {}",
+                record.time, record.kind, record.message
+            );
+            self.records.push_back(record);
+            added += 1;
+        }
+
+        // /logs repeats its whole history on every poll, a message that dropped out of it is
+        // never sent again and can be forgotten
+        if !logs.is_empty() {
+            self.seen = logs
+                .iter()
+                .map(|log| (log.time.clone(), log.message.clone()))
+                .collect();
+        }
+
+        while self.records.len() > MAX_RECORDS {
+            self.records.pop_front();
+        }
+        added
+    }
+}
+
+pub fn collect_game_logs(
+    mut events: EventReader<ApiLogsEvent>,
+    mut game_log: ResMut<GameLog>,
+    game_state: Res<GameState>,
+) {
+    game_log
+        .known_ants
+        .extend(game_state.my_ants.keys().cloned());
+
+    for event in events.read() {
+        let added = game_log.ingest(&event.0, game_state.turn_number);
+        if added > 0 {
+            debug!("Game log: {} new messages", added);
+        }
+    }
+}
+
+pub struct GameLogFilter {
+    search: String,
+    kinds: HashSet<GameLogKind>,
+    selected_ant_only: bool,
+}
+
+impl Default for GameLogFilter {
+    fn default() -> Self {
+        Self {
+            search: String::new(),
+            kinds: GameLogKind::ALL.into_iter().collect(),
+            selected_ant_only: false,
+        }
+    }
+}
+
+impl GameLogFilter {
+    fn matches(&self, record: &GameLogRecord, selected: Option<&String>) -> bool {
+        if !self.kinds.contains(&record.kind) {
+            return false;
+        }
+        if self.selected_ant_only && !selected.is_some_and(|id| record.ant_ids.contains(id)) {
+            return false;
+        }
+        self.search.is_empty()
+            || record
+                .message
+                .to_lowercase()
+                .contains(&self.search.to_lowercase())
+    }
+}
+
+pub fn game_log_console_system(
+    mut contexts: EguiContexts,
+    mut menu_state: ResMut<MenuState>,
+    mut selected: ResMut<SelectedAnt>,
+    game_log: Res<GameLog>,
+    mut filter: Local<GameLogFilter>,
+) -> Result {
+    if !menu_state.show_log_console {
+        return Ok(());
+    }
+
+    let ctx = contexts.ctx_mut()?;
+    let mut open = menu_state.show_log_console;
+    let mut new_selection = None;
+
+    egui::TopBottomPanel::bottom("game_log_console")
+        .resizable(true)
+        .default_height(220.0)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.strong("Game Log");
+                ui.separator();
+                ui.label("Search:");
+                ui.text_edit_singleline(&mut filter.search);
+                ui.checkbox(&mut filter.selected_ant_only, "Selected ant only");
+                if ui.button("Close").clicked() {
+                    open = false;
+                }
+            });
+            ui.horizontal_wrapped(|ui| {
+                for kind in GameLogKind::ALL {
+                    let mut shown = filter.kinds.contains(&kind);
+                    if ui.checkbox(&mut shown, kind.label()).changed() {
+                        if shown {
+                            filter.kinds.insert(kind);
+                        } else {
+                            filter.kinds.remove(&kind);
+                        }
+                    }
+                }
+            });
+            ui.separator();
+
+            let mut by_turn: BTreeMap<i32, Vec<&GameLogRecord>> = BTreeMap::new();
+            for record in game_log.records() {
+                if filter.matches(record, selected.ant_id.as_ref()) {
+                    by_turn.entry(record.turn).or_default().push(record);
+                }
+            }
+
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    if by_turn.is_empty() {
+                        ui.label("No log messages");
+                        return;
+                    }
+
+                    let latest = by_turn.keys().next_back().copied();
+                    for (turn, records) in by_turn.iter().rev() {
+                        egui::CollapsingHeader::new(format!(
+                            "Turn {} ({} messages)",
+                            turn,
+                            records.len()
+                        ))
+                        .id_salt(("game_log_turn", turn))
+                        .default_open(Some(*turn) == latest)
+                        .show(ui, |ui| {
+                            for record in records {
+                                ui.horizontal_wrapped(|ui| {
+                                    ui.weak(&record.time);
+                                    ui.colored_label(record.kind.color(), record.kind.label());
+                                    ui.label(&record.message);
+                                    // Jump to the ant in the inspector
+                                    for ant_id in &record.ant_ids {
+                                        let short = &ant_id[..ant_id.len().min(8)];
+                                        if ui.small_button(short).clicked() {
+                                            new_selection = Some(ant_id.clone());
+                                        }
+                                    }
+                                });
+                            }
+                        });
+                    }
+                });
+        });
+
+    if let Some(id) = new_selection {
+        selected.ant_id = Some(id);
+    }
+    menu_state.show_log_console = open;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicted_messages_are_not_ingested_again() {
+        let logs: Vec<ApiLogMessage> = (0..MAX_RECORDS + 50)
+            .map(|i| ApiLogMessage {
+                message: format!("Ant moved {}", i),
+                time: format!("2025-01-01T00:00:{:05}", i),
+            })
+            .collect();
+        let mut game_log = GameLog::default();
+
+        assert_eq!(game_log.ingest(&logs, 1), MAX_RECORDS + 50);
+        assert_eq!(game_log.records().count(), MAX_RECORDS);
+
+        assert_eq!(game_log.ingest(&logs, 2), 0);
+        assert_eq!(game_log.records().count(), MAX_RECORDS);
+        assert!(game_log.records().all(|record| record.turn == 1));
+    }
+}
@@ -8,7 +8,7 @@ impl HexGeometry {
     pub const SQRT3: f32 = 1.7320508;
 
     pub fn hex_to_world(hex: &HexCoord) -> Vec3 {
-        // "odd-r" horizontal layout
+        // Flat-top "odd-q" layout: odd columns are shifted half a hex down
         let size = Self::SIZE;
         let width = size * 2.0;
         let height = size * Self::SQRT3;
@@ -19,12 +19,29 @@ impl HexGeometry {
         Vec3::new(x, 0.0, z)
     }
 
+    // Inverse of hex_to_world: axial coordinates of the flat-top grid, cube rounded, then
+    // shifted back into the odd column offset that hex_to_world lays out
     pub fn world_to_hex(pos: &Vec3) -> HexCoord {
         let size = Self::SIZE;
-        let q = ((2.0 / 3.0) * pos.x / size).round() as i32;
-        let r = ((-1.0 / 3.0) * pos.x / size + (Self::SQRT3 / 3.0) * pos.z / size).round() as i32;
+        let q = (2.0 / 3.0) * pos.x / size;
+        let r = (-1.0 / 3.0) * pos.x / size + (Self::SQRT3 / 3.0) * pos.z / size;
+        let s = -q - r;
 
-        HexCoord::new(q, r)
+        let (mut round_q, mut round_r, round_s) = (q.round(), r.round(), s.round());
+        let (diff_q, diff_r, diff_s) = (
+            (round_q - q).abs(),
+            (round_r - r).abs(),
+            (round_s - s).abs(),
+        );
+        if diff_q > diff_r && diff_q > diff_s {
+            round_q = -round_r - round_s;
+        } else if diff_r > diff_s {
+            round_r = -round_q - round_s;
+        }
+
+        let q = round_q as i32;
+        let r = round_r as i32;
+        HexCoord::new(q, r + (q - (q & 1)) / 2)
     }
 
     pub fn hex_corners(center: Vec3) -> [Vec3; 6] {
@@ -37,3 +54,30 @@ impl HexGeometry {
         corners
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn world_to_hex_inverts_hex_to_world() {
+        // Even and odd columns on both sides of the origin
+        for q in -5..=5 {
+            for r in -5..=5 {
+                let hex = HexCoord::new(q, r);
+                let world = HexGeometry::hex_to_world(&hex);
+                assert_eq!(HexGeometry::world_to_hex(&world), hex, "at {:?}", world);
+            }
+        }
+    }
+
+    #[test]
+    fn world_to_hex_picks_hex_under_point_near_center() {
+        let hex = HexCoord::new(3, -2);
+        let center = HexGeometry::hex_to_world(&hex);
+        for corner in HexGeometry::hex_corners(center) {
+            let inside = center + (corner - center) * 0.8;
+            assert_eq!(HexGeometry::world_to_hex(&inside), hex);
+        }
+    }
+}
@@ -0,0 +1,376 @@
+use crate::hex_utils::HexGeometry;
+use crate::loot::{LootPile, LootTracker};
+use crate::menu::MenuState;
+use crate::types::*;
+use bevy::prelude::*;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+// Shared strategic layer, recomputed once per turn from the latest GameState
+#[derive(Resource, Default)]
+pub struct InfluenceMap {
+    pub turn_number: Option<i32>,
+    friendly_strength: HashMap<HexCoord, f32>,
+    enemy_threat: HashMap<HexCoord, f32>,
+    anthill_aura: HashMap<HexCoord, AnthillAura>,
+    acid_hazard: HashMap<HexCoord, f32>,
+    food_value: HashMap<HexCoord, f32>,
+    // Enemy anthill hexes never move, so they are remembered for the whole round
+    enemy_anthills: HashSet<HexCoord>,
+    // Food dropped around recent fights, tracked across turns
+    loot: LootTracker,
+    // Hexes our ants failed to enter, avoided by the pathfinder until the given turn
+    learned_obstacles: HashMap<HexCoord, i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnthillAura {
+    Friendly, // Our units get ANTHILL_BONUS here
+    Hostile,  // Our units take ANTHILL_DAMAGE here
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeatmapLayer {
+    None,
+    FriendlyStrength,
+    EnemyThreat,
+    AnthillAura,
+    AcidHazard,
+    FoodValue,
+    Loot,
+}
+
+impl HeatmapLayer {
+    pub const ALL: [HeatmapLayer; 7] = [
+        HeatmapLayer::None,
+        HeatmapLayer::FriendlyStrength,
+        HeatmapLayer::EnemyThreat,
+        HeatmapLayer::AnthillAura,
+        HeatmapLayer::AcidHazard,
+        HeatmapLayer::FoodValue,
+        HeatmapLayer::Loot,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HeatmapLayer::None => "None",
+            HeatmapLayer::FriendlyStrength => "Friendly Strength",
+            HeatmapLayer::EnemyThreat => "Enemy Threat",
+            HeatmapLayer::AnthillAura => "Anthill Aura",
+            HeatmapLayer::AcidHazard => "Acid Hazard",
+            HeatmapLayer::FoodValue => "Food Value",
+            HeatmapLayer::Loot => "Loot",
+        }
+    }
+}
+
+impl InfluenceMap {
+    pub fn friendly_strength(&self, pos: &HexCoord) -> f32 {
+        self.friendly_strength.get(pos).copied().unwrap_or(0.0)
+    }
+
+    pub fn enemy_threat(&self, pos: &HexCoord) -> f32 {
+        self.enemy_threat.get(pos).copied().unwrap_or(0.0)
+    }
+
+    pub fn anthill_aura(&self, pos: &HexCoord) -> Option<AnthillAura> {
+        self.anthill_aura.get(pos).copied()
+    }
+
+    pub fn acid_hazard(&self, pos: &HexCoord) -> f32 {
+        self.acid_hazard.get(pos).copied().unwrap_or(0.0)
+    }
+
+    pub fn food_value(&self, pos: &HexCoord) -> f32 {
+        self.food_value.get(pos).copied().unwrap_or(0.0)
+    }
+
+    pub fn enemy_anthills(&self) -> &HashSet<HexCoord> {
+        &self.enemy_anthills
+    }
+
+    pub fn loot(&self, pos: &HexCoord) -> Option<&LootPile> {
+        self.loot.pile(pos)
+    }
+
+    pub fn loot_piles(&self) -> impl Iterator<Item = (&HexCoord, &LootPile)> {
+        self.loot.piles().iter()
+    }
+
+    pub fn is_learned_obstacle(&self, pos: &HexCoord) -> bool {
+        self.learned_obstacles
+            .get(pos)
+            .is_some_and(|until| self.turn_number.is_none_or(|turn| turn <= *until))
+    }
+
+    pub fn learn_obstacle(&mut self, pos: HexCoord, until_turn: i32) {
+        let until = self.learned_obstacles.entry(pos).or_insert(until_turn);
+        *until = (*until).max(until_turn);
+    }
+
+    /// Damage one of our units takes for ending the turn on this hex
+    pub fn end_of_turn_damage(&self, pos: &HexCoord) -> f32 {
+        let aura_damage = match self.anthill_aura(pos) {
+            Some(AnthillAura::Hostile) => ANTHILL_DAMAGE as f32,
+            _ => 0.0,
+        };
+        self.acid_hazard(pos) + aura_damage
+    }
+
+    /// Positive when we outgun the enemy on this hex, negative when outgunned
+    pub fn control(&self, pos: &HexCoord) -> f32 {
+        self.friendly_strength(pos) - self.enemy_threat(pos)
+    }
+
+    pub fn recompute(&mut self, game_state: &GameState) {
+        // A lower turn number means a new round started, forget the old anthills
+        if self
+            .turn_number
+            .is_some_and(|turn| game_state.turn_number < turn)
+        {
+            self.enemy_anthills.clear();
+            self.learned_obstacles.clear();
+        }
+        self.turn_number = Some(game_state.turn_number);
+        self.learned_obstacles
+            .retain(|_, until| *until >= game_state.turn_number);
+
+        self.friendly_strength.clear();
+        self.enemy_threat.clear();
+        self.anthill_aura.clear();
+        self.acid_hazard.clear();
+        self.food_value.clear();
+
+        let tiles = &game_state.visible_tiles;
+
+        // Friendly strength: every hex one of our ants can attack by the end of this turn
+        for ant in game_state.my_ants.values() {
+            let attack = ant.ant_type.attack() as f32;
+            for pos in Self::attack_reach(ant.position, ant.ant_type.speed(), tiles) {
+                *self.friendly_strength.entry(pos).or_insert(0.0) += attack;
+            }
+        }
+
+        // Enemy threat: same reach computation using the enemy's reported attack
+        for enemy in game_state.enemy_ants.values() {
+            let attack = enemy.attack.max(enemy.ant_type.attack()) as f32;
+            for pos in Self::attack_reach(enemy.position, enemy.ant_type.speed(), tiles) {
+                *self.enemy_threat.entry(pos).or_insert(0.0) += attack;
+            }
+        }
+
+        // Anthill auras. Hostile rings take precedence since they hurt us
+        for tile in tiles.values() {
+            if tile.tile_type == TileType::Anthill
+                && !game_state.home_tiles.contains(&tile.position)
+            {
+                self.enemy_anthills.insert(tile.position);
+            }
+        }
+        for home in &game_state.home_tiles {
+            for pos in Self::hexes_within(*home, ANTHILL_ATTACK_RADIUS) {
+                self.anthill_aura.insert(pos, AnthillAura::Friendly);
+            }
+        }
+        for anthill in &self.enemy_anthills {
+            for pos in Self::hexes_within(*anthill, ANTHILL_ATTACK_RADIUS) {
+                self.anthill_aura.insert(pos, AnthillAura::Hostile);
+            }
+        }
+
+        // Acid hazard
+        for tile in tiles.values() {
+            let damage = tile.tile_type.damage();
+            if damage > 0 {
+                self.acid_hazard.insert(tile.position, damage as f32);
+            }
+        }
+
+        // Food value in calories, ignoring food already sitting in our anthill
+        for food in game_state.food_on_map.values() {
+            if game_state.home_tiles.contains(&food.position) {
+                continue;
+            }
+            self.food_value.insert(
+                food.position,
+                (food.amount * food.food_type.calories()) as f32,
+            );
+        }
+
+        // Loot needs this turn's hazards to tell fights from acid burns
+        let mut loot = std::mem::take(&mut self.loot);
+        loot.update(game_state, |pos| self.end_of_turn_damage(pos));
+        self.loot = loot;
+    }
+
+    pub fn layer_value(&self, layer: HeatmapLayer, pos: &HexCoord) -> f32 {
+        match layer {
+            HeatmapLayer::None => 0.0,
+            HeatmapLayer::FriendlyStrength => self.friendly_strength(pos),
+            HeatmapLayer::EnemyThreat => self.enemy_threat(pos),
+            HeatmapLayer::AnthillAura => match self.anthill_aura(pos) {
+                Some(AnthillAura::Friendly) => ANTHILL_BONUS,
+                Some(AnthillAura::Hostile) => -ANTHILL_BONUS,
+                None => 0.0,
+            },
+            HeatmapLayer::AcidHazard => self.acid_hazard(pos),
+            HeatmapLayer::FoodValue => self.food_value(pos),
+            HeatmapLayer::Loot => self
+                .loot(pos)
+                .map_or(0.0, |pile| (pile.amount * pile.food_type.calories()) as f32),
+        }
+    }
+
+    fn layer_positions(&self, layer: HeatmapLayer) -> Vec<HexCoord> {
+        match layer {
+            HeatmapLayer::None => Vec::new(),
+            HeatmapLayer::FriendlyStrength => self.friendly_strength.keys().copied().collect(),
+            HeatmapLayer::EnemyThreat => self.enemy_threat.keys().copied().collect(),
+            HeatmapLayer::AnthillAura => self.anthill_aura.keys().copied().collect(),
+            HeatmapLayer::AcidHazard => self.acid_hazard.keys().copied().collect(),
+            HeatmapLayer::FoodValue => self.food_value.keys().copied().collect(),
+            HeatmapLayer::Loot => self.loot.piles().keys().copied().collect(),
+        }
+    }
+
+    // Hexes a unit can move to this turn plus their neighbours (units attack adjacent hexes)
+    fn attack_reach(
+        start: HexCoord,
+        movement_points: i32,
+        tiles: &HashMap<HexCoord, Tile>,
+    ) -> HashSet<HexCoord> {
+        let mut best_cost: HashMap<HexCoord, i32> = HashMap::new();
+        let mut queue = BinaryHeap::new();
+
+        best_cost.insert(start, 0);
+        queue.push(Reverse((0, start.q, start.r)));
+
+        while let Some(Reverse((cost, q, r))) = queue.pop() {
+            let current = HexCoord::new(q, r);
+            if best_cost.get(&current).is_some_and(|&best| cost > best) {
+                continue;
+            }
+
+            for neighbor in current.neighbors() {
+                let step = match tiles.get(&neighbor) {
+                    Some(tile) => match tile.tile_type.movement_cost() {
+                        Some(step) => step,
+                        None if tile.tile_type == TileType::Unknown => 1,
+                        None => continue,
+                    },
+                    None => 1, // Assume unexplored tiles are plain
+                };
+
+                let next_cost = cost + step;
+                if next_cost > movement_points {
+                    continue;
+                }
+                if best_cost
+                    .get(&neighbor)
+                    .is_none_or(|&best| next_cost < best)
+                {
+                    best_cost.insert(neighbor, next_cost);
+                    queue.push(Reverse((next_cost, neighbor.q, neighbor.r)));
+                }
+            }
+        }
+
+        let mut reach: HashSet<HexCoord> = best_cost.keys().copied().collect();
+        for pos in best_cost.keys() {
+            reach.extend(pos.neighbors());
+        }
+        reach
+    }
+
+    fn hexes_within(center: HexCoord, radius: i32) -> Vec<HexCoord> {
+        let mut result = vec![center];
+        let mut frontier = vec![center];
+        let mut seen: HashSet<HexCoord> = HashSet::from([center]);
+
+        for _ in 0..radius {
+            let mut next = Vec::new();
+            for pos in &frontier {
+                for neighbor in pos.neighbors() {
+                    if seen.insert(neighbor) {
+                        result.push(neighbor);
+                        next.push(neighbor);
+                    }
+                }
+            }
+            frontier = next;
+        }
+
+        result
+    }
+}
+
+pub fn setup_influence_map(mut commands: Commands) {
+    commands.insert_resource(InfluenceMap::default());
+}
+
+pub fn update_influence_map(game_state: Res<GameState>, mut influence: ResMut<InfluenceMap>) {
+    if !game_state.is_changed() || !game_state.connected {
+        return;
+    }
+
+    // The arena is polled faster than turns advance, only recompute on a new turn
+    if influence.turn_number == Some(game_state.turn_number) {
+        return;
+    }
+
+    influence.recompute(&game_state);
+}
+
+pub fn influence_heatmap_system(
+    mut gizmos: Gizmos,
+    menu_state: Res<MenuState>,
+    influence: Res<InfluenceMap>,
+) {
+    if !menu_state.debug_mode || menu_state.heatmap_layer == HeatmapLayer::None {
+        return;
+    }
+
+    let layer = menu_state.heatmap_layer;
+    let positions = influence.layer_positions(layer);
+    let max_value = positions
+        .iter()
+        .map(|pos| influence.layer_value(layer, pos).abs())
+        .fold(0.0_f32, f32::max);
+
+    if max_value <= 0.0 {
+        return;
+    }
+
+    let corners = HexGeometry::hex_corners(Vec3::ZERO);
+    for pos in positions {
+        let value = influence.layer_value(layer, &pos);
+        let intensity = (value.abs() / max_value).clamp(0.0, 1.0);
+        let color = heatmap_color(layer, value, intensity);
+
+        // Nested hex outlines, more rings for hotter hexes
+        let center = HexGeometry::hex_to_world(&pos) + Vec3::Y * 0.02;
+        let rings = 1 + (intensity * 3.0).round() as i32;
+        for ring in 0..rings {
+            let scale = 0.85 - ring as f32 * 0.18;
+            for i in 0..6 {
+                let start = center + corners[i] * scale;
+                let end = center + corners[(i + 1) % 6] * scale;
+                gizmos.line(start, end, color);
+            }
+        }
+    }
+}
+
+fn heatmap_color(layer: HeatmapLayer, value: f32, intensity: f32) -> Color {
+    let alpha = 0.3 + intensity * 0.7;
+    match layer {
+        HeatmapLayer::FriendlyStrength => Color::srgba(0.1, 0.9, 0.2, alpha),
+        HeatmapLayer::EnemyThreat => Color::srgba(1.0, 0.1, 0.1, alpha),
+        HeatmapLayer::AnthillAura if value >= 0.0 => Color::srgba(0.2, 0.4, 1.0, alpha),
+        HeatmapLayer::AnthillAura => Color::srgba(1.0, 0.4, 0.0, alpha),
+        HeatmapLayer::AcidHazard => Color::srgba(0.8, 0.2, 0.9, alpha),
+        HeatmapLayer::FoodValue => Color::srgba(1.0, 0.9, 0.1, alpha),
+        HeatmapLayer::Loot => Color::srgba(1.0, 0.5, 0.8, alpha),
+        HeatmapLayer::None => Color::NONE,
+    }
+}
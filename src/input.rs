@@ -1,4 +1,5 @@
 use crate::AppConfig;
+use crate::camera_modes::{CameraMode, CameraRig};
 use crate::menu::MenuState;
 use crate::types::*;
 use bevy::input::mouse::{MouseMotion, MouseWheel};
@@ -90,9 +91,11 @@ pub fn camera_movement_system(
     mut drag_state: ResMut<MouseDragState>,
     time: Res<Time>,
     menu_state: Res<MenuState>,
+    camera_rig: Res<CameraRig>,
     windows: Query<&Window>,
 ) {
-    if menu_state.show_menu {
+    // The other modes are driven by camera_rig_system
+    if menu_state.show_menu || camera_rig.mode != CameraMode::Free {
         return;
     }
 
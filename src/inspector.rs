@@ -0,0 +1,387 @@
+use crate::manual_orders::ManualOrders;
+use crate::strategy::{StrategyDecision, StrategyManager};
+use crate::types::*;
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+use chrono::Local;
+use std::collections::{HashMap, VecDeque};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+// Decisions kept per ant for the inspector
+const HISTORY_LEN: usize = 10;
+
+#[derive(Debug, Clone)]
+pub struct DecisionRecord {
+    pub ant_type: AntType,
+    pub position: HexCoord,
+    pub decision: StrategyDecision,
+    pub path: Vec<HexCoord>,
+}
+
+// Recent strategy decisions per ant, flushed to disk once per turn
+#[derive(Resource)]
+pub struct DecisionHistory {
+    per_ant: HashMap<String, VecDeque<DecisionRecord>>,
+    pending: HashMap<String, DecisionRecord>,
+    pending_turn: Option<i32>,
+    log_path: PathBuf,
+}
+
+impl DecisionHistory {
+    pub fn new(log_path: PathBuf) -> Self {
+        Self {
+            per_ant: HashMap::new(),
+            pending: HashMap::new(),
+            pending_turn: None,
+            log_path,
+        }
+    }
+
+    pub fn history(&self, ant_id: &str) -> Option<&VecDeque<DecisionRecord>> {
+        self.per_ant.get(ant_id)
+    }
+
+    fn record(&mut self, ant_id: &str, record: DecisionRecord) {
+        let turn = record.decision.turn_number;
+        let history = self.per_ant.entry(ant_id.to_string()).or_default();
+
+        // Game logic can run more than once per turn, keep only the latest decision
+        if history
+            .back()
+            .is_some_and(|last| last.decision.turn_number == turn)
+        {
+            history.pop_back();
+        }
+        history.push_back(record.clone());
+        while history.len() > HISTORY_LEN {
+            history.pop_front();
+        }
+
+        self.pending.insert(ant_id.to_string(), record);
+    }
+
+    // Append the finished turn as one JSON line
+    fn flush(&mut self) {
+        let Some(turn) = self.pending_turn else {
+            return;
+        };
+        if self.pending.is_empty() {
+            return;
+        }
+
+        let mut ant_ids: Vec<&String> = self.pending.keys().collect();
+        ant_ids.sort();
+
+        let ants: Vec<serde_json::Value> = ant_ids
+            .into_iter()
+            .map(|ant_id| {
+                let record = &self.pending[ant_id];
+                let scores: Vec<serde_json::Value> = record
+                    .decision
+                    .scores
+                    .iter()
+                    .map(|score| {
+                        serde_json::json!({
+                            "strategy": score.strategy,
+                            "base": score.base,
+                            "global": score.global,
+                            "individual": score.individual,
+                            "hysteresis": score.hysteresis,
+                            "total": score.total,
+                        })
+                    })
+                    .collect();
+
+                serde_json::json!({
+                    "ant_id": ant_id,
+                    "ant_type": format!("{:?}", record.ant_type),
+                    "position": [record.position.q, record.position.r],
+                    "chosen": record.decision.chosen,
+                    "committed": record.decision.committed,
+                    "scores": scores,
+                    "path": record.path.iter().map(|pos| [pos.q, pos.r]).collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+
+        let line = serde_json::json!({ "turn": turn, "ants": ants });
+
+        let result = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.log_path)
+            .and_then(|mut file| writeln!(file, "{}", line));
+        if let Err(e) = result {
+            warn!(
+                "Failed to write decisions to {}: {}",
+                self.log_path.display(),
+                e
+            );
+        }
+
+        self.pending.clear();
+    }
+}
+
+pub fn setup_decision_history(mut commands: Commands) {
+    let date = Local::now().format("%Y-%m-%d_%H-%M-%S").to_string();
+    let log_path = PathBuf::from("logs").join(format!("{}_decisions.jsonl", date));
+
+    if let Err(e) = fs::create_dir_all("logs") {
+        warn!("Failed to create logs directory: {}", e);
+    }
+
+    commands.insert_resource(DecisionHistory::new(log_path));
+}
+
+pub fn record_strategy_decisions(
+    mut events: EventReader<StrategyDecisionEvent>,
+    mut round_ended: EventReader<RoundEndedEvent>,
+    mut history: ResMut<DecisionHistory>,
+    game_state: Res<GameState>,
+) {
+    // No next turn is coming to flush the round's last one
+    if round_ended.read().count() > 0 {
+        history.flush();
+    }
+
+    for event in events.read() {
+        let turn = event.decision.turn_number;
+        if history.pending_turn != Some(turn) {
+            history.flush();
+            history.pending_turn = Some(turn);
+
+            // Forget ants that are no longer on the field
+            history
+                .per_ant
+                .retain(|ant_id, _| game_state.my_ants.contains_key(ant_id));
+        }
+
+        history.record(
+            &event.ant_id,
+            DecisionRecord {
+                ant_type: event.ant_type,
+                position: event.position,
+                decision: event.decision.clone(),
+                path: event.path.clone(),
+            },
+        );
+    }
+}
+
+// Runs in Last, after whatever asked the app to exit during the frame
+pub fn flush_decisions_on_exit(
+    mut exits: EventReader<AppExit>,
+    mut history: ResMut<DecisionHistory>,
+) {
+    if exits.read().count() > 0 {
+        history.flush();
+    }
+}
+
+// I cycles the inspected ant
+pub fn ant_selection_hotkey_system(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    game_state: Res<GameState>,
+    mut selected: ResMut<SelectedAnt>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::KeyI) || game_state.my_ants.is_empty() {
+        return;
+    }
+
+    let mut ant_ids: Vec<&String> = game_state.my_ants.keys().collect();
+    ant_ids.sort();
+
+    let next = match &selected.ant_id {
+        Some(current) => ant_ids
+            .iter()
+            .position(|id| *id == current)
+            .map_or(0, |index| (index + 1) % ant_ids.len()),
+        None => 0,
+    };
+    selected.ant_id = Some(ant_ids[next].clone());
+}
+
+pub fn ant_inspector_ui_system(
+    mut contexts: EguiContexts,
+    mut selected: ResMut<SelectedAnt>,
+    game_state: Res<GameState>,
+    history: Res<DecisionHistory>,
+    strategy_manager: Option<Res<StrategyManager>>,
+    mut manual_orders: ResMut<ManualOrders>,
+) -> Result {
+    let Some(ant_id) = selected.ant_id.clone() else {
+        return Ok(());
+    };
+
+    let ctx = contexts.ctx_mut()?;
+    let mut open = true;
+    let mut new_selection = None;
+
+    egui::Window::new("Ant Inspector")
+        .open(&mut open)
+        .default_width(380.0)
+        .resizable(true)
+        .vscroll(true)
+        .show(ctx, |ui| {
+            let mut ant_ids: Vec<&String> = game_state.my_ants.keys().collect();
+            ant_ids.sort();
+            egui::ComboBox::from_label("Ant")
+                .selected_text(ant_id.as_str())
+                .show_ui(ui, |ui| {
+                    for id in ant_ids {
+                        if ui.selectable_label(*id == ant_id, id.as_str()).clicked() {
+                            new_selection = Some(id.clone());
+                        }
+                    }
+                });
+
+            match game_state.my_ants.get(&ant_id) {
+                Some(ant) => {
+                    ui.label(format!(
+                        "{:?} at ({}, {})  HP {}/{}",
+                        ant.ant_type, ant.position.q, ant.position.r, ant.health, ant.max_health
+                    ));
+                    if let Some((food_type, amount)) = ant.food() {
+                        ui.label(format!("Carrying {} {:?}", amount, food_type));
+                    }
+                }
+                None => {
+                    ui.colored_label(egui::Color32::LIGHT_RED, "Ant is no longer on the field");
+                }
+            }
+
+            if let Some(order) = manual_orders.get(&ant_id) {
+                let mut release = false;
+                ui.horizontal(|ui| {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(255, 50, 230),
+                        format!(
+                            "Manual order to ({}, {}) since turn {}, ETA {} turns",
+                            order.target.q,
+                            order.target.r,
+                            order.issued_turn,
+                            order.route.eta()
+                        ),
+                    );
+                    release = ui.small_button("Release").clicked();
+                });
+                if release {
+                    manual_orders.release(&ant_id);
+                }
+            }
+
+            if let Some(plan) = strategy_manager
+                .as_ref()
+                .and_then(|manager| manager.get_ant_plan(&ant_id))
+            {
+                ui.label(format!(
+                    "Plan: '{}' goal {:?}, committed until turn {}",
+                    plan.strategy, plan.goal, plan.committed_until
+                ));
+                if let Some(destination) = plan.goal.and(plan.route.destination()) {
+                    ui.label(format!(
+                        "Route to ({}, {}): {} hexes, ETA {} turns",
+                        destination.q,
+                        destination.r,
+                        plan.route.len(),
+                        plan.route.eta()
+                    ));
+                }
+            }
+
+            ui.separator();
+
+            let records = history.history(&ant_id);
+            match records.and_then(|records| records.back()) {
+                Some(latest) => {
+                    ui.heading(format!("Turn {}", latest.decision.turn_number));
+                    decision_scores_grid(ui, "inspector_latest", &latest.decision);
+                    ui.label(format!("Path: {}", format_path(&latest.path)));
+                }
+                None => {
+                    ui.label("No decisions recorded yet");
+                }
+            }
+
+            if let Some(records) = records {
+                ui.collapsing("History", |ui| {
+                    for record in records.iter().rev() {
+                        let decision = &record.decision;
+                        let winner_total = decision
+                            .scores
+                            .iter()
+                            .find(|score| score.strategy == decision.chosen)
+                            .map_or(0.0, |score| score.total);
+                        let header = format!(
+                            "Turn {}: {} ({:.1}){}",
+                            decision.turn_number,
+                            decision.chosen,
+                            winner_total,
+                            if decision.committed {
+                                " [committed]"
+                            } else {
+                                ""
+                            }
+                        );
+                        ui.collapsing(header, |ui| {
+                            ui.label(format!(
+                                "From ({}, {})",
+                                record.position.q, record.position.r
+                            ));
+                            decision_scores_grid(
+                                ui,
+                                format!("inspector_turn_{}", decision.turn_number),
+                                decision,
+                            );
+                            ui.label(format!("Path: {}", format_path(&record.path)));
+                        });
+                    }
+                });
+            }
+        });
+
+    if let Some(id) = new_selection {
+        selected.ant_id = Some(id);
+    } else if !open {
+        selected.ant_id = None;
+    }
+
+    Ok(())
+}
+
+fn decision_scores_grid(ui: &mut egui::Ui, id: impl std::hash::Hash, decision: &StrategyDecision) {
+    egui::Grid::new(id).striped(true).show(ui, |ui| {
+        for header in ["Strategy", "Base", "Global", "Individual", "Hyst.", "Total"] {
+            ui.strong(header);
+        }
+        ui.end_row();
+
+        for score in &decision.scores {
+            let name = if score.strategy == decision.chosen {
+                egui::RichText::new(format!("▶ {}", score.strategy)).color(egui::Color32::GREEN)
+            } else {
+                egui::RichText::new(score.strategy)
+            };
+            ui.label(name);
+            ui.label(format!("{:.1}", score.base));
+            ui.label(format!("{:.1}", score.global));
+            ui.label(format!("{:.1}", score.individual));
+            ui.label(format!("{:.1}", score.hysteresis));
+            ui.label(format!("{:.1}", score.total));
+            ui.end_row();
+        }
+    });
+}
+
+fn format_path(path: &[HexCoord]) -> String {
+    if path.is_empty() {
+        return "(stay)".to_string();
+    }
+    path.iter()
+        .map(|pos| format!("({}, {})", pos.q, pos.r))
+        .collect::<Vec<_>>()
+        .join(" → ")
+}
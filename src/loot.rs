@@ -0,0 +1,167 @@
+use crate::types::*;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+// Fighting is remembered this many turns when matching new food piles to it
+const COMBAT_MEMORY_TURNS: i32 = 3;
+// Piles out of sight are assumed picked up by the enemy after this many turns
+const LOOT_MEMORY_TURNS: i32 = 10;
+// Cargo drops on the unit's hex, or the nearest free one if another food type lies there
+const DROP_RADIUS: i32 = 2;
+
+#[derive(Debug, Clone)]
+pub struct LootPile {
+    pub food_type: FoodType,
+    pub amount: i32,
+    pub found_turn: i32,
+}
+
+// Diffs consecutive turns to find food dropped by units killed in nearby fights
+#[derive(Default)]
+pub struct LootTracker {
+    previous_turn: Option<i32>,
+    previous_food: HashMap<HexCoord, (FoodType, i32)>,
+    previous_ants: HashMap<String, (HexCoord, i32)>, // Our ants: position and health
+    previous_enemies: Vec<HexCoord>,
+    combat_sites: HashMap<HexCoord, i32>, // Hex -> last turn fighting happened there
+    piles: HashMap<HexCoord, LootPile>,
+}
+
+impl LootTracker {
+    pub fn pile(&self, pos: &HexCoord) -> Option<&LootPile> {
+        self.piles.get(pos)
+    }
+
+    pub fn piles(&self) -> &HashMap<HexCoord, LootPile> {
+        &self.piles
+    }
+
+    pub fn combat_sites(&self) -> impl Iterator<Item = &HexCoord> {
+        self.combat_sites.keys()
+    }
+
+    // `terrain_damage` is what ending the turn on a hex costs, losses it explains are no fight
+    pub fn update(&mut self, game_state: &GameState, terrain_damage: impl Fn(&HexCoord) -> f32) {
+        let turn = game_state.turn_number;
+        match self.previous_turn {
+            Some(previous) if turn == previous => return,
+            Some(previous) if turn < previous => *self = Self::default(),
+            _ => {}
+        }
+        let first_turn = self.previous_turn.is_none();
+
+        if !first_turn {
+            self.record_combat(game_state, turn, &terrain_damage);
+        }
+        self.combat_sites
+            .retain(|_, seen| turn - *seen <= COMBAT_MEMORY_TURNS);
+
+        // Piles that are gone or were picked up
+        self.piles.retain(|pos, pile| {
+            match game_state.food_on_map.get(pos) {
+                Some(food) if food.food_type == pile.food_type => {
+                    pile.amount = food.amount;
+                    true
+                }
+                Some(_) => false,
+                // Piles we cannot currently see may still be there for a while
+                None => {
+                    !game_state.visible_tiles.contains_key(pos)
+                        && turn - pile.found_turn <= LOOT_MEMORY_TURNS
+                }
+            }
+        });
+
+        if !first_turn {
+            for food in game_state.food_on_map.values() {
+                let grew = match self.previous_food.get(&food.position) {
+                    Some((food_type, amount)) => {
+                        *food_type != food.food_type || *amount < food.amount
+                    }
+                    None => true,
+                };
+                if !grew || self.piles.contains_key(&food.position) {
+                    continue;
+                }
+
+                let near_fight = self
+                    .combat_sites
+                    .keys()
+                    .any(|site| site.distance_to(&food.position) <= DROP_RADIUS);
+                if near_fight {
+                    info!(
+                        "Loot spotted at {:?}: {} {:?}",
+                        food.position, food.amount, food.food_type
+                    );
+                    self.piles.insert(
+                        food.position,
+                        LootPile {
+                            food_type: food.food_type,
+                            amount: food.amount,
+                            found_turn: turn,
+                        },
+                    );
+                }
+            }
+        }
+
+        self.previous_turn = Some(turn);
+        self.previous_food = game_state
+            .food_on_map
+            .values()
+            .map(|food| (food.position, (food.food_type, food.amount)))
+            .collect();
+        self.previous_ants = game_state
+            .my_ants
+            .values()
+            .map(|ant| (ant.id.clone(), (ant.position, ant.health)))
+            .collect();
+        self.previous_enemies = game_state
+            .enemy_ants
+            .values()
+            .map(|enemy| enemy.position)
+            .collect();
+    }
+
+    fn record_combat(
+        &mut self,
+        game_state: &GameState,
+        turn: i32,
+        terrain_damage: &impl Fn(&HexCoord) -> f32,
+    ) {
+        // Our ants that died or got hurt by more than acid or an anthill aura accounts for
+        for (id, (position, health)) in &self.previous_ants {
+            let (site, lost) = match game_state.my_ants.get(id) {
+                None => (*position, *health),
+                Some(ant) => (ant.position, *health - ant.health),
+            };
+            if lost > 0 && lost as f32 > terrain_damage(&site) {
+                self.combat_sites.insert(site, turn);
+            }
+        }
+
+        // Hexes our ants struck
+        for ant in game_state.my_ants.values() {
+            if let Some(target) = ant.last_attack {
+                self.combat_sites.insert(target, turn);
+            }
+        }
+
+        // Enemies that vanished from a hex we can still see were likely killed
+        for position in &self.previous_enemies {
+            let still_there = game_state
+                .enemy_ants
+                .values()
+                .any(|enemy| enemy.position.distance_to(position) <= 1);
+            if !still_there && game_state.visible_tiles.contains_key(position) {
+                let our_ant_nearby = game_state
+                    .my_ants
+                    .values()
+                    .any(|ant| ant.position.distance_to(position) <= 1);
+                if our_ant_nearby {
+                    self.combat_sites.insert(*position, turn);
+                }
+            }
+        }
+    }
+}
@@ -1,17 +1,37 @@
+mod analytics;
+mod audit;
+mod camera_modes;
+mod colony;
 mod config;
 mod culling;
+mod fog;
 mod game;
+mod game_log;
 mod hex_utils;
+mod influence;
 mod input;
+mod inspector;
+mod loot;
+mod manual_orders;
 mod menu;
+mod minimap;
+mod overlays;
 mod plugins;
 mod renderer;
 mod rendering;
+mod round_report;
+mod route;
+mod selection;
 mod server;
 mod skybox;
+mod squad;
 mod strategy;
+mod strategy_ui;
+mod terrain;
+mod turn_events;
 mod types;
 mod ui;
+mod unit_hud;
 mod utils;
 
 use bevy::pbr::wireframe::{WireframeConfig, WireframePlugin};
@@ -39,7 +59,7 @@ use types::*;
 
 fn main() -> anyhow::Result<()> {
     // Load configuration
-    let config_path = Path::new("config.toml");
+    let config_path = Path::new(config::CONFIG_PATH);
     let app_config = AppConfig::load_or_create(config_path)?;
 
     // Ensure logs directory exists
@@ -126,6 +146,7 @@ fn main() -> anyhow::Result<()> {
         url: app_config.server.url.clone(),
         token: app_config.server.token.clone(),
         tick_rate: Duration::from_millis(app_config.server.tick_rate_ms),
+        logs_poll_rate: Duration::from_millis(app_config.server.logs_poll_ms),
         auto_reconnect: app_config.server.auto_reconnect,
     };
 
@@ -170,8 +191,11 @@ fn main() -> anyhow::Result<()> {
         ))
         // Custom plugins
         .add_plugins((
+            ConfigPlugin,
             ServerPlugin,
             GamePlugin,
+            ColonyPlugin,
+            InfluencePlugin,
             InputPlugin,
             TemporalAntiAliasPlugin,
             MenuPlugin,
@@ -180,6 +204,19 @@ fn main() -> anyhow::Result<()> {
             SkyboxPlugin,
             OcclusionCullingPlugin,
             RendererPlugin,
+            StrategyUiPlugin,
+            InspectorPlugin,
+        ))
+        // Bevy caps plugin tuples at 15 entries
+        .add_plugins((
+            GameLogPlugin,
+            AnalyticsPlugin,
+            RoundReportPlugin,
+            SelectionPlugin,
+            ManualOrdersPlugin,
+            UnitHudPlugin,
+            MinimapPlugin,
+            CameraModesPlugin,
         ))
         // Resources
         .insert_resource(WireframeConfig {
@@ -0,0 +1,330 @@
+use crate::influence::InfluenceMap;
+use crate::menu::MenuState;
+use crate::route::Route;
+use crate::selection::Selection;
+use crate::types::*;
+use crate::utils::MovementManager;
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+use std::collections::HashMap;
+
+// A right-click that moved further than this was a camera drag
+const CLICK_THRESHOLD: f32 = 6.0;
+const ORDER_COLOR: Color = Color::srgb(1.0, 0.2, 0.9);
+const PREVIEW_COLOR: Color = Color::srgba(1.0, 0.2, 0.9, 0.4);
+
+// Move order given by hand from the viewer
+#[derive(Debug, Clone)]
+pub struct ManualOrder {
+    pub target: HexCoord,
+    pub route: Route,
+    pub issued_turn: i32,
+    from: HexCoord, // Where the ant stood when the last leg was sent
+    sent_turn: Option<i32>,
+}
+
+// Ants under manual control. The strategy layer leaves them alone until they are released
+#[derive(Resource, Default)]
+pub struct ManualOrders {
+    pub command_mode: bool,
+    orders: HashMap<String, ManualOrder>,
+    preview: Option<(HexCoord, Vec<(String, Route)>)>, // Routes to the hovered hex
+    right_press: Option<Vec2>,
+}
+
+impl ManualOrders {
+    pub fn is_locked(&self, ant_id: &str) -> bool {
+        self.orders.contains_key(ant_id)
+    }
+
+    pub fn get(&self, ant_id: &str) -> Option<&ManualOrder> {
+        self.orders.get(ant_id)
+    }
+
+    // Where a locked ant stands once this turn's manual leg is walked, whether or not the
+    // leg has been dispatched yet
+    pub fn leg_end(&self, ant: &Ant) -> Option<HexCoord> {
+        let mut route = self.orders.get(&ant.id)?.route.clone();
+        route.advance_to(ant.position);
+        Some(route.next_leg().last().copied().unwrap_or(ant.position))
+    }
+
+    pub fn orders(&self) -> impl Iterator<Item = (&String, &ManualOrder)> {
+        self.orders.iter()
+    }
+
+    pub fn release(&mut self, ant_id: &str) {
+        if self.orders.remove(ant_id).is_some() {
+            info!("Manual order for ant {} released", ant_id);
+        }
+    }
+
+    // Cheapest route by movement points, dirt is only crossed when going around costs more
+    fn plan(
+        ant: &Ant,
+        target: HexCoord,
+        game_state: &GameState,
+        influence: &InfluenceMap,
+    ) -> Option<Route> {
+        let passable = game_state
+            .visible_tiles
+            .get(&target)
+            .is_none_or(|tile| tile.tile_type.is_passable());
+        if !passable || ant.position == target {
+            return None;
+        }
+        let route = MovementManager::plan_route(ant, target, game_state, influence);
+        (!route.is_empty()).then_some(route)
+    }
+}
+
+// Our ants among the selected entities
+fn selected_ant_ids(selection: &Selection, markers: &Query<&AntMarker>) -> Vec<String> {
+    selection
+        .ants
+        .iter()
+        .filter_map(|entity| markers.get(*entity).ok())
+        .filter(|marker| !marker.is_enemy)
+        .map(|marker| marker.ant_id.clone())
+        .collect()
+}
+
+// C toggles command mode, right-click orders the selected ants to the hovered hex and X
+// hands them back to the strategies
+pub fn manual_order_input_system(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window>,
+    markers: Query<&AntMarker>,
+    selection: Res<Selection>,
+    menu_state: Res<MenuState>,
+    game_state: Res<GameState>,
+    influence: Res<InfluenceMap>,
+    mut manual_orders: ResMut<ManualOrders>,
+) {
+    if menu_state.show_menu {
+        return;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::KeyC) {
+        manual_orders.command_mode = !manual_orders.command_mode;
+        manual_orders.preview = None;
+        info!("Command mode: {}", manual_orders.command_mode);
+    }
+
+    let ant_ids = selected_ant_ids(&selection, &markers);
+    if keyboard_input.just_pressed(KeyCode::KeyX) {
+        for ant_id in &ant_ids {
+            manual_orders.release(ant_id);
+        }
+    }
+
+    if !manual_orders.command_mode {
+        return;
+    }
+
+    // Routes are only worth re-planning when the hovered hex changes
+    let preview_hex = manual_orders.preview.as_ref().map(|(hex, _)| *hex);
+    match selection.hovered_hex {
+        Some(hex) if preview_hex != Some(hex) => {
+            let routes = ant_ids
+                .iter()
+                .filter_map(|id| game_state.my_ants.get(id))
+                .filter_map(|ant| {
+                    ManualOrders::plan(ant, hex, &game_state, &influence)
+                        .map(|route| (ant.id.clone(), route))
+                })
+                .collect();
+            manual_orders.preview = Some((hex, routes));
+        }
+        Some(_) => {}
+        None => manual_orders.preview = None,
+    }
+
+    let cursor = windows.single().ok().and_then(Window::cursor_position);
+    if mouse_button_input.just_pressed(MouseButton::Right) {
+        manual_orders.right_press = cursor;
+    }
+    if !mouse_button_input.just_released(MouseButton::Right) {
+        return;
+    }
+    let pressed_at = manual_orders.right_press.take();
+    let is_click = pressed_at
+        .zip(cursor)
+        .is_some_and(|(start, end)| start.distance(end) < CLICK_THRESHOLD);
+    let Some((target, routes)) = manual_orders.preview.clone().filter(|_| is_click) else {
+        return;
+    };
+
+    if routes.is_empty() {
+        warn!(
+            "No valid path to ({}, {}) for {} selected ants",
+            target.q,
+            target.r,
+            ant_ids.len()
+        );
+        return;
+    }
+    for (ant_id, route) in routes {
+        info!(
+            "Manual order: ant {} to ({}, {}), {} hexes, {} MP, ETA {} turns",
+            ant_id,
+            target.q,
+            target.r,
+            route.len(),
+            route.cost(),
+            route.eta()
+        );
+        let from = game_state.my_ants[&ant_id].position;
+        manual_orders.orders.insert(
+            ant_id,
+            ManualOrder {
+                target,
+                route,
+                issued_turn: game_state.turn_number,
+                from,
+                sent_turn: None,
+            },
+        );
+    }
+}
+
+// Sends each order's next leg once per turn, re-planning when the ant strayed or the route
+// went stale
+pub fn dispatch_manual_orders(
+    game_state: Res<GameState>,
+    influence: Res<InfluenceMap>,
+    mut manual_orders: ResMut<ManualOrders>,
+    mut move_events: EventWriter<MoveCommandEvent>,
+) {
+    if !game_state.connected {
+        return;
+    }
+    let turn = game_state.turn_number;
+
+    let mut finished = Vec::new();
+    for (ant_id, order) in manual_orders.orders.iter_mut() {
+        if order.sent_turn == Some(turn) {
+            continue;
+        }
+        let Some(ant) = game_state.my_ants.get(ant_id) else {
+            info!("Ant {} under manual order is gone", ant_id);
+            finished.push(ant_id.clone());
+            continue;
+        };
+        if ant.position == order.target {
+            info!(
+                "Ant {} reached manual target ({}, {})",
+                ant_id, order.target.q, order.target.r
+            );
+            finished.push(ant_id.clone());
+            continue;
+        }
+
+        let on_route = order.route.advance_to(ant.position) || ant.position == order.from;
+        if !on_route || order.route.is_empty() || order.route.is_stale(&game_state, &influence) {
+            match ManualOrders::plan(ant, order.target, &game_state, &influence) {
+                Some(route) => {
+                    debug!("Ant {} manual route re-planned", ant_id);
+                    order.route = route;
+                }
+                None => {
+                    warn!(
+                        "Ant {} can no longer reach manual target ({}, {})",
+                        ant_id, order.target.q, order.target.r
+                    );
+                    finished.push(ant_id.clone());
+                    continue;
+                }
+            }
+        }
+
+        let path = order.route.next_leg();
+        info!(
+            "Ant {} (type: {:?}) following manual order, path: {:?}",
+            ant_id, ant.ant_type, path
+        );
+        order.from = ant.position;
+        order.sent_turn = Some(turn);
+        move_events.write(MoveCommandEvent {
+            ant_id: ant_id.clone(),
+            path,
+        });
+    }
+
+    for ant_id in finished {
+        manual_orders.release(&ant_id);
+    }
+}
+
+fn draw_route(gizmos: &mut Gizmos, start: HexCoord, route: &Route, color: Color) {
+    let mut prev_pos = hex_to_world_pos(&start) + Vec3::Y * 0.6;
+    for hex_pos in route.hexes() {
+        let world_pos = hex_to_world_pos(hex_pos) + Vec3::Y * 0.6;
+        gizmos.line(prev_pos, world_pos, color);
+        prev_pos = world_pos;
+    }
+}
+
+pub fn draw_manual_orders(
+    mut gizmos: Gizmos,
+    manual_orders: Res<ManualOrders>,
+    game_state: Res<GameState>,
+) {
+    let flat = Isometry3d::from_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2));
+
+    for (ant_id, order) in manual_orders.orders() {
+        let Some(ant) = game_state.my_ants.get(ant_id) else {
+            continue;
+        };
+        draw_route(&mut gizmos, ant.position, &order.route, ORDER_COLOR);
+        let target = hex_to_world_pos(&order.target) + Vec3::Y * 0.05;
+        gizmos.circle(
+            Isometry3d::from_translation(target) * flat,
+            0.6,
+            ORDER_COLOR,
+        );
+    }
+
+    if let Some((_, routes)) = manual_orders.preview.as_ref() {
+        for (ant_id, route) in routes {
+            if let Some(ant) = game_state.my_ants.get(ant_id) {
+                draw_route(&mut gizmos, ant.position, route, PREVIEW_COLOR);
+            }
+        }
+    }
+}
+
+pub fn command_mode_hint_system(
+    mut contexts: EguiContexts,
+    manual_orders: Res<ManualOrders>,
+    selection: Res<Selection>,
+    markers: Query<&AntMarker>,
+) -> Result {
+    if !manual_orders.command_mode {
+        return Ok(());
+    }
+
+    let ctx = contexts.ctx_mut()?;
+    let selected = selected_ant_ids(&selection, &markers).len();
+    egui::Area::new(egui::Id::new("command_mode_hint"))
+        .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 8.0))
+        .interactable(false)
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 50, 230),
+                    format!(
+                        "Command mode: right-click a hex to move {} selected ants. X releases, C exits",
+                        selected
+                    ),
+                );
+                ui.label(format!(
+                    "{} ants under manual control",
+                    manual_orders.orders.len()
+                ));
+            });
+        });
+
+    Ok(())
+}
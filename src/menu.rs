@@ -1,12 +1,16 @@
-use crate::config::AppConfig;
+use crate::config::{AppConfig, CONFIG_PATH};
+use crate::influence::HeatmapLayer;
 use crate::input::CameraController;
+use crate::overlays::OverlayLayer;
 use crate::renderer::RendererSettings;
+use crate::terrain::{BENCHMARK_MAP_SIZE, TerrainBenchmark};
 use crate::types::*;
 use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
 use bevy::pbr::wireframe::WireframeConfig;
 use bevy::prelude::*;
 use bevy::window::{MonitorSelection, PresentMode, WindowMode, WindowResolution};
 use bevy_egui::{EguiContexts, egui};
+use std::collections::HashSet;
 
 #[derive(Resource)]
 pub struct MenuState {
@@ -16,6 +20,13 @@ pub struct MenuState {
     pub show_debug_text: bool,
     pub show_game_state: bool,
     pub debug_mode: bool,
+    pub heatmap_layer: HeatmapLayer,
+    pub show_strategy_panel: bool,
+    pub show_log_console: bool,
+    pub show_analytics_panel: bool,
+    pub show_fog_of_war: bool,
+    pub show_minimap: bool,
+    pub overlay_layers: HashSet<OverlayLayer>,
     pub fov: f32,
     pub selected_resolution: usize,
     pub selected_window_mode: WindowModeWrapper,
@@ -57,6 +68,13 @@ impl Default for MenuState {
             show_debug_text: false, // Hidden by default
             show_game_state: false, // Hidden by default
             debug_mode: false,
+            heatmap_layer: HeatmapLayer::None,
+            show_strategy_panel: false,
+            show_log_console: false,
+            show_analytics_panel: false,
+            show_fog_of_war: true,
+            show_minimap: true,
+            overlay_layers: OverlayLayer::default_layers(),
             fov: 75.0,
             selected_resolution: 2,
             selected_window_mode: WindowModeWrapper::Windowed,
@@ -131,6 +149,7 @@ pub fn menu_ui_system(
     game_state: Res<GameState>,
     connection_state: Res<ConnectionState>,
     diagnostics: Res<DiagnosticsStore>,
+    mut terrain_benchmark: ResMut<TerrainBenchmark>,
 ) -> Result {
     if !menu_state.show_menu {
         return Ok(());
@@ -214,8 +233,8 @@ pub fn menu_ui_system(
                     ));
                     ui.label(format!("Next turn: {:.1}s", game_state.next_turn_in));
                     ui.label(format!(
-                        "Ants: {} (W:{} S:{} Sc:{})",
-                        ant_count, worker_count, soldier_count, scout_count
+                        "Ants: {}/{} (W:{} S:{} Sc:{})",
+                        ant_count, MAX_ANTS, worker_count, soldier_count, scout_count
                     ));
                     ui.label(format!("Enemies: {} | Food: {}", enemy_count, food_count));
                     ui.label(format!(
@@ -247,6 +266,51 @@ pub fn menu_ui_system(
                 );
                 ui.checkbox(&mut menu_state.show_debug_text, "Show Debug Text Overlay");
                 ui.checkbox(&mut menu_state.show_game_state, "Show Game State Overlay");
+                ui.checkbox(&mut menu_state.show_analytics_panel, "Show Analytics Panel");
+                ui.checkbox(&mut menu_state.show_fog_of_war, "Fog of War");
+                ui.checkbox(&mut menu_state.show_minimap, "Show Minimap");
+
+                ui.separator();
+                let label = format!(
+                    "Benchmark Terrain ({}x{})",
+                    BENCHMARK_MAP_SIZE, BENCHMARK_MAP_SIZE
+                );
+                if ui.button(label).clicked() {
+                    terrain_benchmark.requested = true;
+                }
+                if let Some(result) = &terrain_benchmark.last {
+                    ui.label(format!(
+                        "{} tiles, {} chunks: full build {:.2?}, one chunk {:.2?}",
+                        result.tiles, result.chunks, result.full_build, result.chunk_rebuild
+                    ));
+                }
+
+                ui.separator();
+                ui.label("Intent Overlays:");
+                for layer in OverlayLayer::ALL {
+                    let mut shown = menu_state.overlay_layers.contains(&layer);
+                    if ui.checkbox(&mut shown, layer.label()).changed() {
+                        if shown {
+                            menu_state.overlay_layers.insert(layer);
+                        } else {
+                            menu_state.overlay_layers.remove(&layer);
+                        }
+                    }
+                }
+
+                ui.separator();
+                ui.label("Influence Heatmap (debug mode only):");
+                egui::ComboBox::from_id_salt("heatmap_layer_combo")
+                    .selected_text(menu_state.heatmap_layer.label())
+                    .show_ui(ui, |ui| {
+                        for layer in HeatmapLayer::ALL {
+                            ui.selectable_value(
+                                &mut menu_state.heatmap_layer,
+                                layer,
+                                layer.label(),
+                            );
+                        }
+                    });
             });
 
             ui.separator();
@@ -788,12 +852,24 @@ pub fn menu_ui_system(
 
             ui.separator();
 
+            // Strategy Settings
+            ui.collapsing("Strategy", |ui| {
+                ui.checkbox(
+                    &mut menu_state.show_strategy_panel,
+                    "Show Strategy Weights Panel",
+                );
+                ui.label("Tune strategy priorities live, changes apply immediately");
+            });
+
+            ui.separator();
+
             // Server Settings
             ui.collapsing("Server", |ui| {
                 if ui.button("Reconnect to Server").clicked() {
                     reconnect_events.write(ReconnectRequestEvent);
                 }
                 ui.label("Force reconnection to the game server");
+                ui.checkbox(&mut menu_state.show_log_console, "Show Game Log Console");
             });
 
             ui.separator();
@@ -801,7 +877,7 @@ pub fn menu_ui_system(
             // Configuration
             ui.collapsing("Configuration", |ui| {
                 if ui.button("Save Configuration").clicked() {
-                    if let Err(e) = app_config.save(std::path::Path::new("config.toml")) {
+                    if let Err(e) = app_config.save(std::path::Path::new(CONFIG_PATH)) {
                         error!("Failed to save configuration: {}", e);
                     } else {
                         info!("Configuration saved successfully");
@@ -0,0 +1,251 @@
+use crate::fog::{FogOfWar, HexVisibility};
+use crate::input::CameraController;
+use crate::menu::MenuState;
+use crate::rendering::WorldIndex;
+use crate::types::*;
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+
+const MINIMAP_SIZE: f32 = 220.0;
+const MAX_ZOOM: f32 = 8.0;
+// World units of padding around the known map
+const WORLD_MARGIN: f32 = 2.0;
+
+pub struct MinimapState {
+    zoom: f32,
+    show_fog: bool,
+}
+
+impl Default for MinimapState {
+    fn default() -> Self {
+        Self {
+            zoom: 1.0,
+            show_fog: true,
+        }
+    }
+}
+
+fn tile_color(tile_type: TileType) -> egui::Color32 {
+    match tile_type {
+        TileType::Plain => egui::Color32::from_rgb(128, 179, 102),
+        TileType::Dirt => egui::Color32::from_rgb(153, 102, 51),
+        TileType::Acid => egui::Color32::from_rgb(179, 77, 204),
+        TileType::Rock => egui::Color32::from_rgb(128, 128, 128),
+        TileType::Anthill => egui::Color32::from_rgb(102, 77, 204),
+        TileType::Unknown => egui::Color32::from_rgb(77, 77, 77),
+    }
+}
+
+fn food_color(food_type: FoodType) -> egui::Color32 {
+    match food_type {
+        FoodType::Apple => egui::Color32::from_rgb(230, 25, 25),
+        FoodType::Bread => egui::Color32::from_rgb(230, 179, 77),
+        FoodType::Nectar => egui::Color32::from_rgb(255, 230, 25),
+    }
+}
+
+// Maps the world's XZ plane onto the minimap rectangle
+struct MinimapProjection {
+    rect: egui::Rect,
+    center: Vec2,
+    scale: f32, // Minimap points per world unit
+}
+
+impl MinimapProjection {
+    fn to_screen(&self, world: Vec3) -> egui::Pos2 {
+        let offset = (Vec2::new(world.x, world.z) - self.center) * self.scale;
+        self.rect.center() + egui::vec2(offset.x, offset.y)
+    }
+
+    fn to_world(&self, pos: egui::Pos2) -> Vec3 {
+        let offset = (pos - self.rect.center()) / self.scale;
+        let world = self.center + Vec2::new(offset.x, offset.y);
+        Vec3::new(world.x, 0.0, world.y)
+    }
+}
+
+// Where the corners of the screen land on the ground
+fn frustum_footprint(
+    windows: &Query<&Window>,
+    cameras: &Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+) -> Vec<Vec3> {
+    let (Ok(window), Ok((camera, camera_transform))) = (windows.single(), cameras.single()) else {
+        return Vec::new();
+    };
+    let size = camera
+        .logical_viewport_size()
+        .unwrap_or_else(|| window.size());
+
+    [
+        Vec2::ZERO,
+        Vec2::new(size.x, 0.0),
+        size,
+        Vec2::new(0.0, size.y),
+    ]
+    .into_iter()
+    .filter_map(|corner| camera.viewport_to_world(camera_transform, corner).ok())
+    .filter_map(|ray| {
+        ray.intersect_plane(Vec3::ZERO, InfinitePlane3d::new(Vec3::Y))
+            .map(|distance| ray.get_point(distance))
+    })
+    .collect()
+}
+
+pub fn minimap_system(
+    mut contexts: EguiContexts,
+    mut menu_state: ResMut<MenuState>,
+    mut controller: ResMut<CameraController>,
+    mut state: Local<MinimapState>,
+    game_state: Res<GameState>,
+    fog: Res<FogOfWar>,
+    world_index: Res<WorldIndex>,
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+) -> Result {
+    if !menu_state.show_minimap {
+        return Ok(());
+    }
+
+    let ctx = contexts.ctx_mut()?;
+    let mut open = menu_state.show_minimap;
+    let footprint = frustum_footprint(&windows, &cameras);
+
+    egui::Window::new("Minimap")
+        .open(&mut open)
+        .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-8.0, -8.0))
+        .resizable(false)
+        .collapsible(true)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut state.show_fog, "Fog");
+                if ui.small_button("-").clicked() {
+                    state.zoom = (state.zoom / 2.0).max(1.0);
+                }
+                ui.label(format!("{:.0}x", state.zoom));
+                if ui.small_button("+").clicked() {
+                    state.zoom = (state.zoom * 2.0).min(MAX_ZOOM);
+                }
+            });
+
+            let (response, painter) = ui.allocate_painter(
+                egui::vec2(MINIMAP_SIZE, MINIMAP_SIZE),
+                egui::Sense::click_and_drag(),
+            );
+            let rect = response.rect;
+            painter.rect_filled(rect, 0.0, egui::Color32::from_rgb(13, 13, 18));
+
+            let Some(bounds) = world_index.bounds() else {
+                painter.text(
+                    rect.center(),
+                    egui::Align2::CENTER_CENTER,
+                    "No map yet",
+                    egui::FontId::proportional(14.0),
+                    egui::Color32::GRAY,
+                );
+                return;
+            };
+
+            // Fit the known map, or follow the camera when zoomed in
+            let min = hex_to_world_pos(&HexCoord::new(bounds.min_q, bounds.min_r));
+            let max = hex_to_world_pos(&HexCoord::new(bounds.max_q, bounds.max_r));
+            let world_size =
+                Vec2::new(max.x - min.x, max.z - min.z) + Vec2::splat(WORLD_MARGIN * 2.0);
+            let fit = (MINIMAP_SIZE / world_size.x).min(MINIMAP_SIZE / world_size.y);
+            let center = if state.zoom > 1.0 {
+                Vec2::new(controller.target_position.x, controller.target_position.z)
+            } else {
+                Vec2::new(min.x + max.x, min.z + max.z) / 2.0
+            };
+            let projection = MinimapProjection {
+                rect,
+                center,
+                scale: fit * state.zoom,
+            };
+            let painter = painter.with_clip_rect(rect);
+            let hex_radius = (projection.scale * 0.9).max(1.0);
+
+            for (pos, tile_type) in fog.seen_tiles() {
+                let mut color = tile_color(*tile_type);
+                if state.show_fog && fog.visibility(pos) != HexVisibility::Visible {
+                    color = color.gamma_multiply(0.45);
+                }
+                painter.circle_filled(
+                    projection.to_screen(hex_to_world_pos(pos)),
+                    hex_radius,
+                    color,
+                );
+            }
+            for (pos, tile) in &game_state.visible_tiles {
+                painter.circle_filled(
+                    projection.to_screen(hex_to_world_pos(pos)),
+                    hex_radius,
+                    tile_color(tile.tile_type),
+                );
+            }
+
+            for home in &game_state.home_tiles {
+                painter.circle_stroke(
+                    projection.to_screen(hex_to_world_pos(home)),
+                    hex_radius,
+                    egui::Stroke::new(1.5, egui::Color32::from_rgb(26, 26, 230)),
+                );
+            }
+            let dot = (hex_radius * 0.6).max(1.5);
+            for (pos, food) in &game_state.food_on_map {
+                painter.circle_filled(
+                    projection.to_screen(hex_to_world_pos(pos)),
+                    dot * 0.8,
+                    food_color(food.food_type),
+                );
+            }
+            for ant in game_state.my_ants.values() {
+                painter.circle_filled(
+                    projection.to_screen(hex_to_world_pos(&ant.position)),
+                    dot,
+                    egui::Color32::WHITE,
+                );
+            }
+            for enemy in game_state.enemy_ants.values() {
+                painter.circle_filled(
+                    projection.to_screen(hex_to_world_pos(&enemy.position)),
+                    dot,
+                    egui::Color32::RED,
+                );
+            }
+
+            if footprint.len() == 4 {
+                let points: Vec<egui::Pos2> = footprint
+                    .iter()
+                    .map(|corner| projection.to_screen(*corner))
+                    .collect();
+                painter.add(egui::Shape::closed_line(
+                    points,
+                    egui::Stroke::new(1.0, egui::Color32::YELLOW),
+                ));
+            }
+
+            if response.hovered() {
+                let scroll = ui.input(|input| input.smooth_scroll_delta.y);
+                if scroll > 0.0 {
+                    state.zoom = (state.zoom * 1.1).min(MAX_ZOOM);
+                } else if scroll < 0.0 {
+                    state.zoom = (state.zoom / 1.1).max(1.0);
+                }
+            }
+
+            // Click or drag to move the camera there
+            if response.clicked() || response.dragged() {
+                if let Some(pointer) = response.interact_pointer_pos() {
+                    let target = projection.to_world(pointer);
+                    let hex = world_pos_to_hex(&target);
+                    let world = hex_to_world_pos(&hex);
+                    controller.target_position.x = world.x;
+                    controller.target_position.z = world.z;
+                }
+            }
+        });
+
+    menu_state.show_minimap = open;
+
+    Ok(())
+}
@@ -0,0 +1,235 @@
+use crate::hex_utils::HexGeometry;
+use crate::influence::InfluenceMap;
+use crate::manual_orders::ManualOrders;
+use crate::menu::MenuState;
+use crate::rendering::WorldIndex;
+use crate::strategy::{PlanGoal, StrategyManager};
+use crate::types::*;
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+const PATH_HEIGHT: f32 = 0.5;
+const DASH_LENGTH: f32 = 0.25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverlayLayer {
+    PlannedPaths,
+    Routes,
+    Targets,
+    EnemyMovement,
+    AnthillRings,
+}
+
+impl OverlayLayer {
+    pub const ALL: [OverlayLayer; 5] = [
+        OverlayLayer::PlannedPaths,
+        OverlayLayer::Routes,
+        OverlayLayer::Targets,
+        OverlayLayer::EnemyMovement,
+        OverlayLayer::AnthillRings,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            OverlayLayer::PlannedPaths => "Planned Paths (this turn)",
+            OverlayLayer::Routes => "Multi-turn Routes",
+            OverlayLayer::Targets => "Goal Targets",
+            OverlayLayer::EnemyMovement => "Enemy Movement",
+            OverlayLayer::AnthillRings => "Anthill Radii",
+        }
+    }
+
+    pub fn default_layers() -> HashSet<OverlayLayer> {
+        [OverlayLayer::PlannedPaths, OverlayLayer::Targets]
+            .into_iter()
+            .collect()
+    }
+}
+
+// Paths sent to the server this turn, by ant
+#[derive(Resource, Default)]
+pub struct PlannedPaths {
+    turn: i32,
+    paths: HashMap<String, Vec<HexCoord>>,
+}
+
+pub fn record_planned_paths(
+    game_state: Res<GameState>,
+    mut planned: ResMut<PlannedPaths>,
+    mut move_events: EventReader<MoveCommandEvent>,
+) {
+    if planned.turn != game_state.turn_number {
+        planned.turn = game_state.turn_number;
+        planned.paths.clear();
+    }
+    for event in move_events.read() {
+        planned
+            .paths
+            .insert(event.ant_id.clone(), event.path.clone());
+    }
+}
+
+fn strategy_color(strategy: &str) -> Color {
+    match strategy {
+        "Explore" => Color::srgb(0.0, 0.9, 1.0),
+        "Gather" => Color::srgb(1.0, 0.85, 0.1),
+        "Defend" => Color::srgb(0.3, 0.4, 1.0),
+        "Attack" => Color::srgb(1.0, 0.25, 0.2),
+        _ => Color::srgb(0.8, 0.8, 0.8),
+    }
+}
+
+fn path_point(hex: &HexCoord) -> Vec3 {
+    hex_to_world_pos(hex) + Vec3::Y * PATH_HEIGHT
+}
+
+fn dashed_line(gizmos: &mut Gizmos, start: Vec3, end: Vec3, color: Color) {
+    let length = start.distance(end);
+    if length <= f32::EPSILON {
+        return;
+    }
+    let dashes = (length / (DASH_LENGTH * 2.0)).ceil().max(1.0) as usize;
+    for i in 0..dashes {
+        let from = i as f32 * DASH_LENGTH * 2.0;
+        let to = (from + DASH_LENGTH).min(length);
+        gizmos.line(
+            start.lerp(end, from / length),
+            start.lerp(end, to / length),
+            color,
+        );
+    }
+}
+
+// Hex radius in world units, measured to the outer edge of the last ring of hexes
+fn ring_radius(hexes: i32) -> f32 {
+    (hexes as f32 + 0.5) * HexGeometry::SQRT3 * HexGeometry::SIZE
+}
+
+fn draw_target(gizmos: &mut Gizmos, target: HexCoord, strategy: &str, goal: Option<PlanGoal>) {
+    let center = hex_to_world_pos(&target) + Vec3::Y * 0.05;
+    let flat = Isometry3d::from_translation(center)
+        * Isometry3d::from_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2));
+    let color = strategy_color(strategy);
+
+    match goal {
+        Some(PlanGoal::Food(_)) => {
+            gizmos.circle(flat, 0.35, color);
+        }
+        Some(PlanGoal::Defend(_)) => {
+            gizmos.circle(flat, 0.5, color);
+            gizmos.circle(flat, 0.3, color);
+        }
+        Some(PlanGoal::Home(_)) => {
+            gizmos.rect(flat, Vec2::splat(0.6), color);
+        }
+        // Raids get a cross, exploration frontiers a diamond
+        Some(PlanGoal::Attack(_)) => {
+            let size = 0.4;
+            gizmos.line(
+                center + Vec3::new(-size, 0.0, -size),
+                center + Vec3::new(size, 0.0, size),
+                color,
+            );
+            gizmos.line(
+                center + Vec3::new(-size, 0.0, size),
+                center + Vec3::new(size, 0.0, -size),
+                color,
+            );
+        }
+        Some(PlanGoal::Explore(_)) | None => {
+            let corners =
+                [Vec3::X, Vec3::Z, Vec3::NEG_X, Vec3::NEG_Z].map(|dir| center + dir * 0.4);
+            gizmos.linestrip(corners.into_iter().chain([corners[0]]), color);
+        }
+    }
+}
+
+pub fn draw_intent_overlays(
+    mut gizmos: Gizmos,
+    menu_state: Res<MenuState>,
+    game_state: Res<GameState>,
+    influence: Res<InfluenceMap>,
+    planned: Res<PlannedPaths>,
+    world_index: Res<WorldIndex>,
+    manual_orders: Res<ManualOrders>,
+    strategy_manager: Option<Res<StrategyManager>>,
+) {
+    let layers = &menu_state.overlay_layers;
+    if layers.is_empty() || !game_state.connected {
+        return;
+    }
+
+    for (ant_id, ant) in &game_state.my_ants {
+        // Manual orders have their own overlay
+        if manual_orders.is_locked(ant_id) {
+            continue;
+        }
+        let plan = strategy_manager
+            .as_ref()
+            .and_then(|manager| manager.get_ant_plan(ant_id));
+        let strategy = plan.map_or("", |plan| plan.strategy);
+        let color = strategy_color(strategy);
+
+        let path = planned.paths.get(ant_id);
+        if let Some(path) = path.filter(|_| layers.contains(&OverlayLayer::PlannedPaths)) {
+            let points = std::iter::once(&ant.position).chain(path).map(path_point);
+            gizmos.linestrip(points, color);
+        }
+
+        let Some(plan) = plan else {
+            continue;
+        };
+        if layers.contains(&OverlayLayer::Routes) {
+            // Later legs continue from where this turn's leg stops
+            let stop = path.and_then(|path| path.last()).unwrap_or(&ant.position);
+            let mut prev = path_point(stop);
+            for hex in plan.route.later_legs() {
+                let next = path_point(hex);
+                dashed_line(&mut gizmos, prev, next, color.with_alpha(0.6));
+                prev = next;
+            }
+        }
+        if layers.contains(&OverlayLayer::Targets) {
+            let target = plan
+                .goal
+                .map(|goal| goal.position())
+                .or(plan.route.destination().copied());
+            if let Some(target) = target {
+                draw_target(&mut gizmos, target, plan.strategy, plan.goal);
+            }
+        }
+    }
+
+    if layers.contains(&OverlayLayer::EnemyMovement) {
+        for (from, to) in world_index.enemy_moves() {
+            gizmos.arrow(
+                path_point(&from),
+                path_point(&to),
+                Color::srgb(1.0, 0.3, 0.1),
+            );
+        }
+    }
+
+    if layers.contains(&OverlayLayer::AnthillRings) {
+        let rotation = Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2);
+        let ring = |gizmos: &mut Gizmos, hex: &HexCoord, color: Color| {
+            let center = hex_to_world_pos(hex) + Vec3::Y * 0.05;
+            gizmos.circle(
+                Isometry3d::new(center, rotation),
+                ring_radius(ANTHILL_ATTACK_RADIUS),
+                color,
+            );
+        };
+        // Our anthill both shoots enemies and buffs our units inside the same radius
+        if !game_state.home_tiles.is_empty() {
+            ring(
+                &mut gizmos,
+                &game_state.main_spot,
+                Color::srgb(0.2, 1.0, 0.4),
+            );
+        }
+        for anthill in influence.enemy_anthills() {
+            ring(&mut gizmos, anthill, Color::srgba(1.0, 0.1, 0.1, 0.7));
+        }
+    }
+}
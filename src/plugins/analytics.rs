@@ -0,0 +1,19 @@
+use crate::analytics::*;
+use crate::turn_events::{detect_round_end, emit_turn_events};
+use bevy::prelude::*;
+use bevy_egui::EguiPrimaryContextPass;
+
+pub struct AnalyticsPlugin;
+
+impl Plugin for AnalyticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Analytics>()
+            .add_systems(
+                Update,
+                record_analytics
+                    .after(emit_turn_events)
+                    .after(detect_round_end),
+            )
+            .add_systems(EguiPrimaryContextPass, analytics_panel_system);
+    }
+}
@@ -0,0 +1,22 @@
+use crate::camera_modes::*;
+use crate::input::camera_movement_system;
+use bevy::prelude::*;
+use bevy_egui::EguiPrimaryContextPass;
+
+pub struct CameraModesPlugin;
+
+impl Plugin for CameraModesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraRig>()
+            .add_systems(
+                Update,
+                (
+                    camera_mode_input_system,
+                    camera_rig_system
+                        .after(camera_mode_input_system)
+                        .after(camera_movement_system),
+                ),
+            )
+            .add_systems(EguiPrimaryContextPass, camera_toolbar_system);
+    }
+}
@@ -0,0 +1,11 @@
+use crate::colony::*;
+use bevy::prelude::*;
+
+pub struct ColonyPlugin;
+
+impl Plugin for ColonyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpawnTracker>()
+            .add_systems(Update, track_spawns);
+    }
+}
@@ -0,0 +1,11 @@
+use crate::config::*;
+use bevy::prelude::*;
+
+pub struct ConfigPlugin;
+
+impl Plugin for ConfigPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_config_watcher)
+            .add_systems(Update, watch_config_file);
+    }
+}
@@ -1,4 +1,7 @@
+use crate::audit::*;
 use crate::game::*;
+use crate::influence::update_influence_map;
+use crate::strategy::StrategyRegistry;
 use crate::types::*;
 use bevy::prelude::*;
 
@@ -10,8 +13,19 @@ impl Plugin for GamePlugin {
             // Add game-specific events
             .add_event::<GameActionEvent>()
             .add_event::<MoveCommandEvent>()
+            .add_event::<PlanInvalidatedEvent>()
+            .add_event::<StrategyDecisionEvent>()
+            // Built-in strategies, other plugins can add theirs with register_strategy
+            .init_resource::<StrategyRegistry>()
+            .init_resource::<ExecutionAuditor>()
             // Add game systems
             .add_systems(Startup, setup_game_logic)
-            .add_systems(Update, game_logic_system);
+            .add_systems(Update, (sync_strategy_config, game_logic_system).chain())
+            .add_systems(
+                Update,
+                audit_plan_execution
+                    .after(update_influence_map)
+                    .before(game_logic_system),
+            );
     }
 }
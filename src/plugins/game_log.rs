@@ -0,0 +1,13 @@
+use crate::game_log::*;
+use bevy::prelude::*;
+use bevy_egui::EguiPrimaryContextPass;
+
+pub struct GameLogPlugin;
+
+impl Plugin for GameLogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameLog>()
+            .add_systems(Update, collect_game_logs)
+            .add_systems(EguiPrimaryContextPass, game_log_console_system);
+    }
+}
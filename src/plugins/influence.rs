@@ -0,0 +1,17 @@
+use crate::game::game_logic_system;
+use crate::influence::*;
+use bevy::prelude::*;
+
+pub struct InfluencePlugin;
+
+impl Plugin for InfluencePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_influence_map).add_systems(
+            Update,
+            (
+                update_influence_map.before(game_logic_system),
+                influence_heatmap_system,
+            ),
+        );
+    }
+}
@@ -0,0 +1,19 @@
+use crate::inspector::*;
+use crate::types::*;
+use bevy::prelude::*;
+use bevy_egui::EguiPrimaryContextPass;
+
+pub struct InspectorPlugin;
+
+impl Plugin for InspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SelectedAnt>()
+            .add_systems(Startup, setup_decision_history)
+            .add_systems(
+                Update,
+                (ant_selection_hotkey_system, record_strategy_decisions),
+            )
+            .add_systems(Last, flush_decisions_on_exit)
+            .add_systems(EguiPrimaryContextPass, ant_inspector_ui_system);
+    }
+}
@@ -0,0 +1,21 @@
+use crate::manual_orders::*;
+use crate::selection::pick_under_cursor;
+use bevy::prelude::*;
+use bevy_egui::EguiPrimaryContextPass;
+
+pub struct ManualOrdersPlugin;
+
+impl Plugin for ManualOrdersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ManualOrders>()
+            .add_systems(
+                Update,
+                (
+                    manual_order_input_system.after(pick_under_cursor),
+                    dispatch_manual_orders.after(manual_order_input_system),
+                    draw_manual_orders,
+                ),
+            )
+            .add_systems(EguiPrimaryContextPass, command_mode_hint_system);
+    }
+}
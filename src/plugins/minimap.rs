@@ -0,0 +1,11 @@
+use crate::minimap::*;
+use bevy::prelude::*;
+use bevy_egui::EguiPrimaryContextPass;
+
+pub struct MinimapPlugin;
+
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(EguiPrimaryContextPass, minimap_system);
+    }
+}
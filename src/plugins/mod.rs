@@ -1,19 +1,45 @@
+pub mod analytics;
+pub mod camera_modes;
+pub mod colony;
+pub mod config;
 pub mod culling;
 pub mod game;
+pub mod game_log;
+pub mod influence;
 pub mod input;
+pub mod inspector;
+pub mod manual_orders;
 pub mod menu;
+pub mod minimap;
 pub mod renderer;
 pub mod rendering;
+pub mod round_report;
+pub mod selection;
 pub mod server;
 pub mod skybox;
+pub mod strategy_ui;
 pub mod ui;
+pub mod unit_hud;
 
+pub use analytics::AnalyticsPlugin;
+pub use camera_modes::CameraModesPlugin;
+pub use colony::ColonyPlugin;
+pub use config::ConfigPlugin;
 pub use culling::OcclusionCullingPlugin;
 pub use game::GamePlugin;
+pub use game_log::GameLogPlugin;
+pub use influence::InfluencePlugin;
 pub use input::InputPlugin;
+pub use inspector::InspectorPlugin;
+pub use manual_orders::ManualOrdersPlugin;
 pub use menu::MenuPlugin;
+pub use minimap::MinimapPlugin;
 pub use renderer::RendererPlugin;
 pub use rendering::RenderingPlugin;
+pub use round_report::RoundReportPlugin;
+pub use selection::SelectionPlugin;
 pub use server::ServerPlugin;
 pub use skybox::SkyboxPlugin;
+pub use strategy_ui::StrategyUiPlugin;
 pub use ui::UiPlugin;
+pub use unit_hud::UnitHudPlugin;
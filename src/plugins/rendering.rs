@@ -1,17 +1,29 @@
+use crate::fog::*;
+use crate::overlays::*;
 use crate::rendering::*;
+use crate::terrain::*;
 use bevy::prelude::*;
 
 pub struct RenderingPlugin;
 
 impl Plugin for RenderingPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup_3d_scene)
+        app.init_resource::<WorldIndex>()
+            .init_resource::<FogOfWar>()
+            .init_resource::<PlannedPaths>()
+            .init_resource::<TerrainBenchmark>()
+            .add_systems(Startup, setup_3d_scene)
             .add_systems(
                 Update,
                 (
-                    update_world_rendering,
+                    update_fog_of_war,
+                    reconcile_world.after(update_fog_of_war),
+                    animate_unit_movement.after(reconcile_world),
                     debug_rendering_system,
+                    record_planned_paths,
+                    draw_intent_overlays.after(record_planned_paths),
                     update_camera_focus,
+                    run_terrain_benchmark,
                 ),
             )
             .add_observer(change_material);
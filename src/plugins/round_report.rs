@@ -0,0 +1,19 @@
+use crate::analytics::record_analytics;
+use crate::round_report::*;
+use crate::turn_events::{detect_round_end, emit_turn_events};
+use bevy::prelude::*;
+
+pub struct RoundReportPlugin;
+
+impl Plugin for RoundReportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RoundRecorder>().add_systems(
+            Update,
+            // Must see the finished round before the analytics reset for the next one
+            round_report_system
+                .after(emit_turn_events)
+                .after(detect_round_end)
+                .before(record_analytics),
+        );
+    }
+}
@@ -0,0 +1,19 @@
+use crate::selection::*;
+use bevy::prelude::*;
+use bevy_egui::EguiPrimaryContextPass;
+
+pub struct SelectionPlugin;
+
+impl Plugin for SelectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Selection>()
+            .add_systems(
+                Update,
+                (
+                    pick_under_cursor,
+                    draw_selection_highlights.after(pick_under_cursor),
+                ),
+            )
+            .add_systems(EguiPrimaryContextPass, selection_overlay_system);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::server::*;
+use crate::turn_events::*;
 use crate::types::*;
 use bevy::prelude::*;
 
@@ -11,8 +12,25 @@ impl Plugin for ServerPlugin {
             .add_event::<ApiArenaEvent>()
             .add_event::<ApiMoveEvent>()
             .add_event::<ApiRegistrationEvent>()
+            .add_event::<ApiLogsEvent>()
+            .add_event::<ApiMoveErrorsEvent>()
             .add_event::<ConnectionEvent>()
             .add_event::<ReconnectRequestEvent>()
+            // Typed events from diffing consecutive turns
+            .add_event::<AntSpawnedEvent>()
+            .add_event::<AntDiedEvent>()
+            .add_event::<AntDamagedEvent>()
+            .add_event::<FoodCollectedEvent>()
+            .add_event::<FoodDeliveredEvent>()
+            .add_event::<FoodAppearedEvent>()
+            .add_event::<FoodDisappearedEvent>()
+            .add_event::<EnemySightedEvent>()
+            .add_event::<EnemyLostEvent>()
+            .add_event::<ScoreChangedEvent>()
+            .add_event::<PathInterruptedEvent>()
+            .add_event::<RoundEndedEvent>()
+            .init_resource::<TurnTracker>()
+            .init_resource::<RoundTracker>()
             // Add server systems
             .add_systems(Startup, (setup_server_client, setup_rate_limiter))
             .add_systems(
@@ -30,6 +48,16 @@ impl Plugin for ServerPlugin {
                     monitor_connection_system,
                     auto_move_system,
                 ),
+            )
+            .add_systems(
+                Update,
+                (
+                    emit_turn_events
+                        .after(handle_arena_state_tasks)
+                        .after(handle_move_response_tasks),
+                    log_turn_events.after(emit_turn_events),
+                    detect_round_end.after(handle_arena_state_tasks),
+                ),
             );
     }
 }
@@ -0,0 +1,11 @@
+use crate::strategy_ui::*;
+use bevy::prelude::*;
+use bevy_egui::EguiPrimaryContextPass;
+
+pub struct StrategyUiPlugin;
+
+impl Plugin for StrategyUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(EguiPrimaryContextPass, strategy_weights_ui_system);
+    }
+}
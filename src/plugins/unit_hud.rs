@@ -0,0 +1,11 @@
+use crate::unit_hud::*;
+use bevy::prelude::*;
+
+pub struct UnitHudPlugin;
+
+impl Plugin for UnitHudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_unit_hud_assets)
+            .add_systems(Update, update_unit_huds);
+    }
+}
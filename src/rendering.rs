@@ -1,16 +1,24 @@
+use crate::fog::{FogOfWar, HexVisibility};
 use crate::input::CameraController;
 use crate::menu::MenuState;
+use crate::terrain::{TerrainChunks, TerrainPalette};
+use crate::turn_events::match_enemies;
 use crate::types::*;
 use bevy::color::palettes;
 use bevy::math::prelude::*;
 use bevy::prelude::*;
 use bevy::scene::SceneInstanceReady;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 
 #[derive(Resource)]
 pub struct RenderingAssets {
     pub food_materials: HashMap<FoodType, Handle<StandardMaterial>>,
     pub tile_materials: HashMap<TileType, Handle<StandardMaterial>>,
+    pub terrain_palette: TerrainPalette,
+    pub terrain_material: Handle<StandardMaterial>,
+    pub ghost_food_materials: HashMap<FoodType, Handle<StandardMaterial>>,
     pub home_material: Handle<StandardMaterial>,
     pub ground_material: Handle<StandardMaterial>,
     pub ant_model: Handle<Scene>,
@@ -149,6 +157,33 @@ pub fn setup_3d_scene(
         }),
     );
 
+    // Terrain chunks are colored per vertex, from the same colors as the tile materials
+    let tile_colors = tile_materials
+        .iter()
+        .filter_map(|(tile_type, handle)| {
+            materials
+                .get(handle)
+                .map(|material| (*tile_type, material.base_color))
+        })
+        .collect();
+    let terrain_palette = TerrainPalette::new(tile_colors, Color::srgb(0.05, 0.05, 0.07));
+    let terrain_material = materials.add(StandardMaterial {
+        base_color: Color::WHITE,
+        metallic: 0.0,
+        perceptual_roughness: 0.85,
+        ..default()
+    });
+
+    // Ghost food for the fog of war is see-through
+    let ghost_food_materials = food_materials
+        .iter()
+        .map(|(food_type, handle)| {
+            let mut material = materials.get(handle).cloned().unwrap_or_default();
+            material.base_color = material.base_color.with_alpha(0.35);
+            material.alpha_mode = AlphaMode::Blend;
+            (*food_type, materials.add(material))
+        })
+        .collect();
     let home_material = materials.add(StandardMaterial {
         base_color: Color::srgb(0.1, 0.1, 0.9),
         emissive: LinearRgba::new(0.0, 0.0, 0.3, 1.0),
@@ -167,6 +202,9 @@ pub fn setup_3d_scene(
     commands.insert_resource(RenderingAssets {
         food_materials,
         tile_materials,
+        terrain_palette,
+        terrain_material,
+        ghost_food_materials,
         home_material,
         ground_material,
         ant_model,
@@ -176,67 +214,377 @@ pub fn setup_3d_scene(
     });
 }
 
-pub fn update_world_rendering(
+// Health is shown by the unit HUD, so models keep one size. Enemies are drawn a bit larger
+const ANT_MODEL_SCALE: f32 = 0.006;
+const ENEMY_MODEL_SCALE: f32 = 0.007;
+
+// Unknown hexes drawn around everything we have seen
+const GRID_MARGIN: i32 = 5;
+
+// Offset coordinate rectangle covering the known part of the map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridBounds {
+    pub min_q: i32,
+    pub max_q: i32,
+    pub min_r: i32,
+    pub max_r: i32,
+}
+
+impl GridBounds {
+    fn around(pos: HexCoord) -> Self {
+        Self {
+            min_q: pos.q,
+            max_q: pos.q,
+            min_r: pos.r,
+            max_r: pos.r,
+        }
+    }
+
+    fn include(&mut self, pos: HexCoord) {
+        self.min_q = self.min_q.min(pos.q);
+        self.max_q = self.max_q.max(pos.q);
+        self.min_r = self.min_r.min(pos.r);
+        self.max_r = self.max_r.max(pos.r);
+    }
+
+    fn padded(&self, margin: i32) -> Self {
+        Self {
+            min_q: self.min_q - margin,
+            max_q: self.max_q + margin,
+            min_r: self.min_r - margin,
+            max_r: self.max_r + margin,
+        }
+    }
+
+    fn hexes(&self) -> impl Iterator<Item = HexCoord> + use<> {
+        let (min_q, max_q) = (self.min_q, self.max_q);
+        (self.min_r..=self.max_r)
+            .flat_map(move |r| (min_q..=max_q).map(move |q| HexCoord::new(q, r)))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct EnemyTrack {
+    id: String,
+    ant_type: AntType,
+    position: HexCoord,
+    moved_from: Option<HexCoord>, // Where it stood the turn before, if it moved
+    turn: i32,
+}
+
+// Entities spawned for the game state, so each turn only touches what changed
+#[derive(Resource, Default)]
+pub struct WorldIndex {
+    ants: HashMap<String, Entity>,
+    enemies: HashMap<String, Entity>, // Keyed by track id, enemy ids change as they move
+    enemy_tracks: Vec<EnemyTrack>,
+    next_track: u32,
+    food: HashMap<HexCoord, Entity>,
+    terrain: TerrainChunks,
+    homes: HashMap<HexCoord, Entity>,
+    main_spot: Option<HexCoord>,
+    bounds: Option<GridBounds>,
+    visible: HashSet<HexCoord>, // Tiles that were visible when we last reconciled
+    fog_enabled: bool,
+    last_turn: Option<i32>,
+    fingerprint: Option<u64>, // Of the state last reconciled, see world_fingerprint
+}
+
+impl WorldIndex {
+    pub fn bounds(&self) -> Option<GridBounds> {
+        self.bounds
+    }
+
+    // Enemy moves from last turn to this one, as (from, to)
+    pub fn enemy_moves(&self) -> impl Iterator<Item = (HexCoord, HexCoord)> {
+        self.enemy_tracks
+            .iter()
+            .filter_map(|track| track.moved_from.map(|from| (from, track.position)))
+    }
+
+    // Grows the known bounds, returns true if they changed
+    fn include_known(&mut self, game_state: &GameState) -> bool {
+        let before = self.bounds;
+        let known = game_state
+            .visible_tiles
+            .keys()
+            .chain(game_state.home_tiles.iter());
+        for pos in known {
+            match self.bounds.as_mut() {
+                Some(bounds) => bounds.include(*pos),
+                None => self.bounds = Some(GridBounds::around(*pos)),
+            }
+        }
+        self.bounds != before
+    }
+
+    // Gives every visible enemy a stable id by following it from last turn
+    fn track_enemies<'a>(&mut self, game_state: &'a GameState) -> Vec<(String, &'a Enemy)> {
+        let mut enemies: Vec<&Enemy> = game_state.enemy_ants.values().collect();
+        enemies.sort_by_key(|enemy| (enemy.position.q, enemy.position.r));
+
+        let previous: Vec<(AntType, HexCoord)> = self
+            .enemy_tracks
+            .iter()
+            .map(|track| (track.ant_type, track.position))
+            .collect();
+        let matches = match_enemies(&previous, &enemies);
+
+        let turn = game_state.turn_number;
+        let mut tracks = Vec::with_capacity(enemies.len());
+        let mut tracked = Vec::with_capacity(enemies.len());
+        for (enemy, matched) in enemies.into_iter().zip(matches) {
+            let before = matched.map(|index| &self.enemy_tracks[index]);
+            let id = match before {
+                Some(track) => track.id.clone(),
+                None => {
+                    self.next_track += 1;
+                    format!("enemy_{}", self.next_track)
+                }
+            };
+            // Reconciling twice in a turn must not forget the move
+            let moved_from = before.and_then(|track| {
+                if track.position != enemy.position {
+                    Some(track.position)
+                } else if track.turn == turn {
+                    track.moved_from
+                } else {
+                    None
+                }
+            });
+            tracks.push(EnemyTrack {
+                id: id.clone(),
+                ant_type: enemy.ant_type,
+                position: enemy.position,
+                moved_from,
+                turn,
+            });
+            tracked.push((id, enemy));
+        }
+
+        self.enemy_tracks = tracks;
+        tracked
+    }
+
+    // A new round may be played on a different map
+    fn clear(&mut self, commands: &mut Commands) {
+        let entities = self
+            .ants
+            .values()
+            .chain(self.enemies.values())
+            .chain(self.food.values())
+            .chain(self.homes.values());
+        for entity in entities {
+            commands.entity(*entity).despawn();
+        }
+        self.terrain.despawn(commands);
+        *self = Self::default();
+    }
+}
+
+pub fn reconcile_world(
     mut commands: Commands,
     game_state: Res<GameState>,
     rendering_assets: Res<RenderingAssets>,
-    ant_query: Query<Entity, (With<AntMarker>, Without<PersistentHex>)>,
-    food_query: Query<Entity, (With<FoodMarker>, Without<PersistentHex>)>,
-    home_query: Query<Entity, (With<HomeMarker>, Without<PersistentHex>)>,
-    existing_hex_query: Query<(Entity, &TileMarker), With<PersistentHex>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut index: ResMut<WorldIndex>,
+    mut units: Query<(&mut Transform, &mut MoveAnimation), With<AntMarker>>,
+    fog: Res<FogOfWar>,
+    menu_state: Res<MenuState>,
 ) {
-    // Clear dynamic entities
-    for entity in ant_query.iter() {
-        commands.entity(entity).despawn();
+    let fog = menu_state.show_fog_of_war.then_some(&*fog);
+    let fog_toggled = index.fog_enabled != fog.is_some();
+    if !game_state.is_changed() && !fog_toggled {
+        return;
     }
-    for entity in food_query.iter() {
-        commands.entity(entity).despawn();
+
+    // Every arena poll touches the state, most of them within a turn repeat what we drew
+    let fingerprint = world_fingerprint(&game_state);
+    if index.fingerprint == Some(fingerprint) && !fog_toggled {
+        return;
     }
-    for entity in home_query.iter() {
-        commands.entity(entity).despawn();
+
+    if index
+        .last_turn
+        .is_some_and(|last| game_state.turn_number < last)
+    {
+        index.clear(&mut commands);
     }
+    index.last_turn = Some(game_state.turn_number);
+    index.fingerprint = Some(fingerprint);
+
+    reconcile_tiles(
+        &mut commands,
+        &mut meshes,
+        &game_state,
+        fog,
+        &rendering_assets,
+        &mut index,
+    );
+    reconcile_homes(&mut commands, &game_state, &rendering_assets, &mut index);
+    reconcile_ants(
+        &mut commands,
+        &game_state,
+        &rendering_assets,
+        &mut index,
+        &mut units,
+    );
+    reconcile_food(
+        &mut commands,
+        &game_state,
+        fog,
+        &rendering_assets,
+        &mut index,
+    );
+}
 
-    // Create a comprehensive hex grid
-    let grid_size = 50; // Adjust as needed
-    let existing_hexes: HashMap<HexCoord, Entity> = existing_hex_query
-        .iter()
-        .map(|(entity, marker)| (marker.position, entity))
-        .collect();
+// Cheap summary of everything the reconciler draws
+fn world_fingerprint(game_state: &GameState) -> u64 {
+    fn hash_of(value: impl Hash) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+    // Map iteration order is arbitrary, so combine the items order independently
+    fn sum_of(values: impl Iterator<Item = impl Hash>) -> u64 {
+        values.map(hash_of).fold(0, u64::wrapping_add)
+    }
 
-    // Generate grid in odd-r layout
-    for r in -grid_size..=grid_size {
-        for q in -grid_size..=grid_size {
-            let hex_pos = HexCoord::new(q, r);
-            let world_pos = hex_pos_to_world_oddr(hex_pos);
-
-            // Determine hex type and material
-            let (tile_type, material) =
-                determine_hex_appearance(&hex_pos, &game_state, &rendering_assets);
-
-            // Update existing hex or create new one
-            if let Some(entity) = existing_hexes.get(&hex_pos) {
-                // Update existing hex with new material
-                commands.entity(*entity).insert(MeshMaterial3d(material));
-            } else {
-                // Create new hex
-                commands.spawn((
-                    Mesh3d(rendering_assets.hex_mesh.clone()),
-                    MeshMaterial3d(material),
-                    Transform::from_translation(world_pos).with_scale(Vec3::splat(0.95)),
-                    TileMarker {
-                        tile_type,
-                        position: hex_pos,
-                    },
-                    PersistentHex,
-                ));
+    hash_of((
+        game_state.turn_number,
+        game_state.connected,
+        game_state.main_spot,
+        &game_state.home_tiles,
+        sum_of(game_state.my_ants.values().map(|ant| {
+            (
+                &ant.id,
+                ant.position,
+                ant.health,
+                ant.food.amount,
+                &ant.last_move,
+            )
+        })),
+        sum_of(
+            game_state
+                .enemy_ants
+                .values()
+                .map(|enemy| (enemy.ant_type, enemy.position, enemy.health)),
+        ),
+        sum_of(
+            game_state
+                .food_on_map
+                .values()
+                .map(|food| (food.position, food.amount)),
+        ),
+        sum_of(
+            game_state
+                .visible_tiles
+                .values()
+                .map(|tile| (tile.position, tile.tile_type)),
+        ),
+    ))
+}
+
+// Share of the time until the next turn spent walking, so ants arrive before it starts
+const MOVE_TIME_SHARE: f32 = 0.8;
+const MIN_MOVE_SECONDS: f32 = 0.2;
+const MAX_MOVE_SECONDS: f32 = 2.0;
+// How fast ants turn to face where they are going, in radians per second
+const TURN_SPEED: f32 = 10.0;
+
+// Walks an ant model along the hexes it moved through last turn
+#[derive(Component, Debug, Clone)]
+pub struct MoveAnimation {
+    waypoints: Vec<Vec3>, // Starts where the model stood when the move began
+    hex: HexCoord,        // Hex of the last waypoint
+    elapsed: f32,
+    duration: f32,
+}
+
+impl MoveAnimation {
+    fn standing(position: Vec3, hex: HexCoord) -> Self {
+        Self {
+            waypoints: vec![position],
+            hex,
+            elapsed: 0.0,
+            duration: 0.0,
+        }
+    }
+
+    fn destination(&self) -> Vec3 {
+        self.waypoints[self.waypoints.len() - 1]
+    }
+
+    fn start(&mut self, from: Vec3, via: Vec<Vec3>, to: Vec3, hex: HexCoord, duration: f32) {
+        self.waypoints = std::iter::once(from)
+            .chain(via)
+            .chain(std::iter::once(to))
+            .collect();
+        self.hex = hex;
+        self.elapsed = 0.0;
+        self.duration = duration;
+    }
+
+    pub fn is_moving(&self) -> bool {
+        self.elapsed < self.duration
+    }
+
+    // Position along the path and the direction of travel there. Progress is eased and
+    // spread by distance, so the speed is even across hexes of any spacing
+    fn sample(&self) -> (Vec3, Option<Vec3>) {
+        let t = if self.duration > 0.0 {
+            (self.elapsed / self.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        let eased = t * t * (3.0 - 2.0 * t);
+
+        let total: f32 = self
+            .waypoints
+            .windows(2)
+            .map(|pair| pair[0].distance(pair[1]))
+            .sum();
+        let mut remaining = eased * total;
+
+        for pair in self.waypoints.windows(2) {
+            let length = pair[0].distance(pair[1]);
+            if length <= f32::EPSILON {
+                continue;
+            }
+            let direction = (pair[1] - pair[0]).with_y(0.0).try_normalize();
+            if remaining <= length {
+                return (pair[0].lerp(pair[1], remaining / length), direction);
             }
+            remaining -= length;
         }
+
+        (self.destination(), None)
     }
+}
+
+fn move_duration(game_state: &GameState) -> f32 {
+    (game_state.next_turn_in * MOVE_TIME_SHARE).clamp(MIN_MOVE_SECONDS, MAX_MOVE_SECONDS)
+}
 
-    // Continue with rendering other entities
-    render_home_tiles(&mut commands, &game_state, &rendering_assets);
-    render_ants(&mut commands, &game_state, &rendering_assets);
-    render_food(&mut commands, &game_state, &rendering_assets);
+pub fn animate_unit_movement(
+    time: Res<Time>,
+    mut units: Query<(&mut Transform, &mut MoveAnimation), With<AntMarker>>,
+) {
+    for (mut transform, mut animation) in units.iter_mut() {
+        if !animation.is_moving() {
+            continue;
+        }
+        animation.elapsed += time.delta_secs();
+
+        let (position, direction) = animation.sample();
+        transform.translation = position;
+        if let Some(direction) = direction {
+            let facing = transform.looking_to(direction, Vec3::Y).rotation;
+            transform.rotation = transform
+                .rotation
+                .slerp(facing, (TURN_SPEED * time.delta_secs()).min(1.0));
+        }
+    }
 }
 
 pub fn render_default_hex_grid(
@@ -331,22 +679,16 @@ pub fn debug_rendering_system(
 
 pub fn update_camera_focus(
     game_state: Res<GameState>,
-    mut camera_query: Query<&mut Transform, (With<GameCamera>, Without<AntMarker>)>,
     input: Res<ButtonInput<KeyCode>>,
-    controller: Res<CameraController>,
+    mut controller: ResMut<CameraController>,
 ) {
     if !game_state.connected {
         return;
     }
 
-    // Focus camera on main spot when F is pressed
+    // Focus camera on main spot when F is pressed, every camera mode moves towards the target
     if input.just_pressed(KeyCode::KeyF) {
-        let center = hex_pos_to_world_oddr(game_state.main_spot);
-
-        if let Ok(mut camera_transform) = camera_query.single_mut() {
-            camera_transform.translation = Vec3::new(center.x, controller.current_zoom, center.z);
-            camera_transform.look_at(center, Vec3::Y);
-        }
+        controller.target_position = hex_pos_to_world_oddr(game_state.main_spot);
     }
 }
 
@@ -386,158 +728,344 @@ pub fn change_material(
     }
 }
 
-// Separate function for rendering home tiles
-fn render_home_tiles(
+fn despawn_missing<K>(
+    commands: &mut Commands,
+    entities: &mut HashMap<K, Entity>,
+    keep: impl Fn(&K) -> bool,
+) {
+    entities.retain(|key, entity| {
+        let alive = keep(key);
+        if !alive {
+            commands.entity(*entity).despawn();
+        }
+        alive
+    });
+}
+
+fn reconcile_tiles(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    game_state: &GameState,
+    fog: Option<&FogOfWar>,
+    rendering_assets: &RenderingAssets,
+    index: &mut WorldIndex,
+) {
+    let grew = index.include_known(game_state);
+    let Some(bounds) = index.bounds else {
+        return;
+    };
+
+    // Only tiles that are or were visible can change, plus new ones when the map grows. Turning
+    // the fog on or off repaints everything
+    let mut visible: HashSet<HexCoord> = game_state.visible_tiles.keys().copied().collect();
+    if let Some(fog) = fog {
+        visible.extend(fog.vision());
+    }
+    let mut dirty: HashSet<HexCoord> = if index.fog_enabled != fog.is_some() {
+        index.terrain.hexes().collect()
+    } else {
+        visible.union(&index.visible).copied().collect()
+    };
+    if grew {
+        dirty.extend(
+            bounds
+                .padded(GRID_MARGIN)
+                .hexes()
+                .filter(|pos| !index.terrain.contains(pos)),
+        );
+    }
+
+    // Only the chunks holding tiles that changed color are rebuilt
+    for hex_pos in dirty {
+        let color =
+            determine_hex_color(&hex_pos, game_state, fog, &rendering_assets.terrain_palette);
+        index.terrain.set(hex_pos, color);
+    }
+    let rebuilt = index
+        .terrain
+        .rebuild(commands, meshes, &rendering_assets.terrain_material);
+    if rebuilt > 0 {
+        debug!("Rebuilt {} terrain chunks", rebuilt);
+    }
+
+    index.visible = visible;
+    index.fog_enabled = fog.is_some();
+}
+
+fn reconcile_homes(
     commands: &mut Commands,
     game_state: &GameState,
     rendering_assets: &RenderingAssets,
+    index: &mut WorldIndex,
 ) {
+    // Only the old and the new main spot change looks when it moves
+    let previous_main = index.main_spot.replace(game_state.main_spot);
+    let main_moved = previous_main != Some(game_state.main_spot);
+
     for home_pos in &game_state.home_tiles {
         let position = hex_pos_to_world_oddr(*home_pos) + Vec3::Y * 0.15;
         let is_main = *home_pos == game_state.main_spot;
         let scale = if is_main { 1.3 } else { 1.1 };
+        let transform = Transform::from_translation(position).with_scale(Vec3::splat(scale));
+        let marker = HomeMarker {
+            is_main_spot: is_main,
+        };
 
-        commands.spawn((
-            Mesh3d(rendering_assets.home_mesh.clone()),
-            MeshMaterial3d(rendering_assets.home_material.clone()),
-            Transform::from_translation(position).with_scale(Vec3::splat(scale)),
-            HomeMarker {
-                is_main_spot: is_main,
-            },
-        ));
+        match index.homes.get(home_pos) {
+            Some(entity) => {
+                if main_moved && (is_main || previous_main == Some(*home_pos)) {
+                    commands.entity(*entity).insert((transform, marker));
+                }
+            }
+            None => {
+                let entity = commands
+                    .spawn((
+                        Mesh3d(rendering_assets.home_mesh.clone()),
+                        MeshMaterial3d(rendering_assets.home_material.clone()),
+                        transform,
+                        marker,
+                    ))
+                    .id();
+                index.homes.insert(*home_pos, entity);
+            }
+        }
     }
+
+    despawn_missing(commands, &mut index.homes, |pos| {
+        game_state.home_tiles.contains(pos)
+    });
 }
 
-// Separate function for rendering ants
-fn render_ants(
+// Where an ant model should stand this turn and how it gets there
+struct UnitPlacement {
+    hex: HexCoord,
+    via: Vec<Vec3>, // Hexes walked on the way, empty for enemies
+    destination: Vec3,
+    scale: f32,
+    stats: UnitStats,
+}
+
+fn reconcile_ants(
     commands: &mut Commands,
     game_state: &GameState,
     rendering_assets: &RenderingAssets,
+    index: &mut WorldIndex,
+    units: &mut Query<(&mut Transform, &mut MoveAnimation), With<AntMarker>>,
 ) {
-    // Count units per hex for proper displacement
-    let mut units_per_hex: HashMap<HexCoord, Vec<(String, UnitType)>> = HashMap::new();
+    let enemies = index.track_enemies(game_state);
+    let duration = move_duration(game_state);
+
+    // Sorted so units sharing a hex keep their spot from turn to turn
+    let mut my_ants: Vec<&Ant> = game_state.my_ants.values().collect();
+    my_ants.sort_by(|a, b| a.id.cmp(&b.id));
 
-    for (ant_id, ant) in &game_state.my_ants {
+    // Count units per hex for proper displacement
+    let mut units_per_hex: HashMap<HexCoord, Vec<(&str, UnitType)>> = HashMap::new();
+    for ant in &my_ants {
         units_per_hex
             .entry(ant.position)
-            .or_insert_with(Vec::new)
-            .push((ant_id.clone(), UnitType::Ant));
+            .or_default()
+            .push((ant.id.as_str(), UnitType::Ant));
     }
-
-    for (enemy_id, enemy) in &game_state.enemy_ants {
+    for (track_id, enemy) in &enemies {
         units_per_hex
             .entry(enemy.position)
-            .or_insert_with(Vec::new)
-            .push((enemy_id.clone(), UnitType::Enemy));
+            .or_default()
+            .push((track_id.as_str(), UnitType::Enemy));
     }
 
-    // Render my ants
-    for (ant_id, ant) in &game_state.my_ants {
-        let units_on_hex = units_per_hex.get(&ant.position).unwrap();
-        let ant_index = units_on_hex
+    let unit_position = |id: &str, unit_type: UnitType, pos: HexCoord| {
+        let units_on_hex = &units_per_hex[&pos];
+        let unit_index = units_on_hex
             .iter()
-            .position(|(id, t)| id == ant_id && *t == UnitType::Ant)
+            .position(|(other, t)| *other == id && *t == unit_type)
             .unwrap_or(0);
+        hex_pos_to_world_oddr(pos)
+            + Vec3::Y * 0.3
+            + get_unit_offset(unit_index, unit_type, units_on_hex.len())
+    };
 
-        let base_position = hex_pos_to_world_oddr(ant.position) + Vec3::Y * 0.3;
-        let offset = get_unit_offset(ant_index, UnitType::Ant, units_on_hex.len());
-        let position = base_position + offset;
-
-        let health_ratio = ant.health as f32 / ant.ant_type.health() as f32;
-        let scale = (0.8 + health_ratio * 0.5) * 0.005;
-
-        let ant_marker = AntMarker {
-            ant_id: ant_id.clone(),
+    for ant in &my_ants {
+        let placement = UnitPlacement {
+            hex: ant.position,
+            via: ant
+                .last_move
+                .iter()
+                .filter(|hex| **hex != ant.position)
+                .map(|hex| hex_pos_to_world_oddr(*hex) + Vec3::Y * 0.3)
+                .collect(),
+            destination: unit_position(ant.id.as_str(), UnitType::Ant, ant.position),
+            scale: ANT_MODEL_SCALE,
+            stats: UnitStats {
+                health: ant.health,
+                max_health: ant.max_health.max(ant.ant_type.health()),
+                cargo: ant.food(),
+                capacity: ant.ant_type.capacity(),
+                attack: None,
+            },
+        };
+        let marker = AntMarker {
+            ant_id: ant.id.clone(),
             ant_type: ant.ant_type,
             is_enemy: false,
         };
-        if let Some(color) = get_ant_color(&ant_marker) {
-            commands.spawn((
-                SceneRoot(rendering_assets.ant_model.clone()),
-                Transform::from_translation(position).with_scale(Vec3::splat(scale)),
-                ColorOverride(color),
-                ant_marker,
-            ));
-        } else {
-            commands.spawn((
-                SceneRoot(rendering_assets.ant_model.clone()),
-                Transform::from_translation(position).with_scale(Vec3::splat(scale)),
-                ant_marker,
-            ));
-        }
-    }
-
-    // Render enemy ants
-    for (enemy_id, enemy) in &game_state.enemy_ants {
-        let units_on_hex = units_per_hex.get(&enemy.position).unwrap();
-        let enemy_index = units_on_hex
-            .iter()
-            .position(|(id, t)| id == enemy_id && *t == UnitType::Enemy)
-            .unwrap_or(0);
 
-        let base_position = hex_pos_to_world_oddr(enemy.position) + Vec3::Y * 0.3;
-        let offset = get_unit_offset(enemy_index, UnitType::Enemy, units_on_hex.len());
-        let position = base_position + offset;
-
-        let health_ratio = enemy.health as f32 / enemy.ant_type.health() as f32;
-        let scale = (1.0 + health_ratio * 0.5) * 0.005;
+        let entity = upsert_ant(
+            commands,
+            units,
+            index.ants.get(&ant.id).copied(),
+            rendering_assets,
+            placement,
+            duration,
+            marker,
+        );
+        index.ants.insert(ant.id.clone(), entity);
+    }
 
-        let ant_marker = AntMarker {
-            ant_id: enemy_id.clone(),
+    for (track_id, enemy) in &enemies {
+        let placement = UnitPlacement {
+            hex: enemy.position,
+            via: Vec::new(),
+            destination: unit_position(track_id.as_str(), UnitType::Enemy, enemy.position),
+            scale: ENEMY_MODEL_SCALE,
+            stats: UnitStats {
+                health: enemy.health,
+                max_health: enemy.ant_type.health().max(enemy.health),
+                cargo: (enemy.food.amount > 0).then_some((enemy.food.food_type, enemy.food.amount)),
+                capacity: enemy.ant_type.capacity(),
+                attack: Some(enemy.attack),
+            },
+        };
+        let marker = AntMarker {
+            ant_id: track_id.clone(),
             ant_type: enemy.ant_type,
             is_enemy: true,
         };
-        if let Some(color) = get_ant_color(&ant_marker) {
-            commands.spawn((
-                SceneRoot(rendering_assets.ant_model.clone()),
-                Transform::from_translation(position).with_scale(Vec3::splat(scale)),
-                ColorOverride(color),
-                ant_marker,
-            ));
-        } else {
-            commands.spawn((
-                SceneRoot(rendering_assets.ant_model.clone()),
-                Transform::from_translation(position).with_scale(Vec3::splat(scale)),
-                ant_marker,
-            ));
+
+        let entity = upsert_ant(
+            commands,
+            units,
+            index.enemies.get(track_id).copied(),
+            rendering_assets,
+            placement,
+            duration,
+            marker,
+        );
+        index.enemies.insert(track_id.clone(), entity);
+    }
+
+    despawn_missing(commands, &mut index.ants, |id| {
+        game_state.my_ants.contains_key(id)
+    });
+    despawn_missing(commands, &mut index.enemies, |id| {
+        enemies.iter().any(|(track_id, _)| track_id == id)
+    });
+}
+
+// Sends an existing ant model on its way, or spawns one. An id keeps its ant type, so the
+// tint set at spawn stays valid
+fn upsert_ant(
+    commands: &mut Commands,
+    units: &mut Query<(&mut Transform, &mut MoveAnimation), With<AntMarker>>,
+    existing: Option<Entity>,
+    rendering_assets: &RenderingAssets,
+    placement: UnitPlacement,
+    duration: f32,
+    marker: AntMarker,
+) -> Entity {
+    if let Some(entity) = existing {
+        if let Ok((mut transform, mut animation)) = units.get_mut(entity) {
+            transform.scale = Vec3::splat(placement.scale);
+            if animation.destination() != placement.destination {
+                // Only walk lastMove when the ant changed hex, not when its spot on the hex
+                // moved to make room for another unit
+                let via = if animation.hex != placement.hex {
+                    placement.via
+                } else {
+                    Vec::new()
+                };
+                animation.start(
+                    transform.translation,
+                    via,
+                    placement.destination,
+                    placement.hex,
+                    duration,
+                );
+            }
         }
-        commands.spawn((
-            SceneRoot(rendering_assets.ant_model.clone()),
-            Transform::from_translation(position).with_scale(Vec3::splat(scale)),
-            AntMarker {
-                ant_id: enemy_id.clone(),
-                ant_type: enemy.ant_type,
-                is_enemy: true,
-            },
-        ));
+        commands.entity(entity).insert((marker, placement.stats));
+        return entity;
     }
+
+    let color = get_ant_color(&marker);
+    let mut entity = commands.spawn((
+        SceneRoot(rendering_assets.ant_model.clone()),
+        Transform::from_translation(placement.destination).with_scale(Vec3::splat(placement.scale)),
+        MoveAnimation::standing(placement.destination, placement.hex),
+        marker,
+        placement.stats,
+    ));
+    if let Some(color) = color {
+        entity.insert(ColorOverride(color));
+    }
+    entity.id()
 }
 
-// Separate function for rendering food
-fn render_food(
+fn reconcile_food(
     commands: &mut Commands,
     game_state: &GameState,
+    fog: Option<&FogOfWar>,
     rendering_assets: &RenderingAssets,
+    index: &mut WorldIndex,
 ) {
-    for (pos, food) in &game_state.food_on_map {
+    // Food out of sight is drawn where we last saw it
+    let visible = game_state
+        .food_on_map
+        .iter()
+        .map(|(pos, food)| (pos, food, &rendering_assets.food_materials));
+    let ghosts = fog
+        .into_iter()
+        .flat_map(FogOfWar::ghost_food)
+        .filter(|(pos, _)| !game_state.food_on_map.contains_key(pos))
+        .map(|(pos, food)| (pos, food, &rendering_assets.ghost_food_materials));
+    let mut shown = HashSet::new();
+
+    for (pos, food, materials) in visible.chain(ghosts) {
+        let Some(material) = materials.get(&food.food_type) else {
+            continue;
+        };
+        shown.insert(*pos);
         let position = hex_pos_to_world_oddr(*pos) + Vec3::Y * 0.2;
+        let scale = 0.6 + (food.amount as f32 / 10.0).min(0.8);
+        let transform = Transform::from_translation(position).with_scale(Vec3::splat(scale));
+        let marker = FoodMarker {
+            food_type: food.food_type,
+            amount: food.amount,
+        };
 
-        if let Some(material) = rendering_assets.food_materials.get(&food.food_type) {
-            let mut transform = Transform::from_translation(position);
-            let scale = 0.6 + (food.amount as f32 / 10.0).min(0.8);
-            transform.scale = Vec3::splat(scale);
-
-            commands.spawn((
-                Mesh3d(rendering_assets.food_mesh.clone()),
-                MeshMaterial3d(material.clone()),
-                transform,
-                FoodMarker {
-                    food_type: food.food_type,
-                    amount: food.amount,
-                },
-            ));
+        match index.food.get(pos) {
+            Some(entity) => {
+                commands.entity(*entity).insert((
+                    transform,
+                    MeshMaterial3d(material.clone()),
+                    marker,
+                ));
+            }
+            None => {
+                let entity = commands
+                    .spawn((
+                        Mesh3d(rendering_assets.food_mesh.clone()),
+                        MeshMaterial3d(material.clone()),
+                        transform,
+                        marker,
+                    ))
+                    .id();
+                index.food.insert(*pos, entity);
+            }
         }
     }
+
+    despawn_missing(commands, &mut index.food, |pos| shown.contains(pos));
 }
 
 fn create_proper_hexagon_mesh() -> Mesh {
@@ -582,28 +1110,26 @@ fn create_proper_hexagon_mesh() -> Mesh {
     .with_inserted_indices(bevy::render::mesh::Indices::U32(indices))
 }
 
-fn determine_hex_appearance(
+fn determine_hex_color(
     hex_pos: &HexCoord,
     game_state: &GameState,
-    rendering_assets: &RenderingAssets,
-) -> (TileType, Handle<StandardMaterial>) {
-    // Check if hex is visible in game state
+    fog: Option<&FogOfWar>,
+    palette: &TerrainPalette,
+) -> Color {
+    // Visible tile - use actual tile type
     if let Some(tile) = game_state.visible_tiles.get(hex_pos) {
-        // Visible tile - use actual tile type
-        let material = rendering_assets
-            .tile_materials
-            .get(&tile.tile_type)
-            .unwrap_or(&rendering_assets.tile_materials[&TileType::Plain])
-            .clone();
-        (tile.tile_type, material)
-    } else {
-        // Not visible - use gray material for unknown tiles
-        let material = rendering_assets
-            .tile_materials
-            .get(&TileType::Unknown)
-            .unwrap_or(&rendering_assets.tile_materials[&TileType::Plain])
-            .clone();
-        (TileType::Unknown, material)
+        return palette.visible(tile.tile_type);
+    }
+
+    // Without fog of war anything out of sight is the same gray
+    let Some(fog) = fog else {
+        return palette.visible(TileType::Unknown);
+    };
+
+    match (fog.visibility(hex_pos), fog.remembered_tile(hex_pos)) {
+        (HexVisibility::NeverSeen, _) | (_, None) => palette.fog(),
+        // In vision range but the server sent nothing for it, show what we remember
+        (_, Some(tile_type)) => palette.remembered(tile_type),
     }
 }
 
@@ -0,0 +1,374 @@
+use crate::analytics::Analytics;
+use crate::influence::{AnthillAura, InfluenceMap};
+use crate::types::*;
+use bevy::prelude::*;
+use chrono::{DateTime, Local};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+
+// Turns per column of the strategy mix table
+const MIX_WINDOW: i32 = 50;
+const TOP_MOVE_ERRORS: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DeathCause {
+    Combat,
+    EnemyAnthill,
+    Acid,
+}
+
+// Collects what the end-of-round report needs that the analytics do not already keep
+#[derive(Resource, Default)]
+pub struct RoundRecorder {
+    realm: Option<String>,
+    team: Option<String>,
+    started: Option<DateTime<Local>>,
+    first_turn: Option<i32>,
+    last_turn: Option<i32>,
+    final_score: i32,
+    losses: BTreeMap<(String, DeathCause), u32>, // (ant type, cause) -> count
+    raids_performed: u32,
+    raids_suffered: u32,
+    raiding: bool,
+    raided: bool,
+    strategy_by_turn: BTreeMap<i32, HashMap<String, &'static str>>,
+    move_errors: HashMap<String, u32>,
+    written: bool,
+}
+
+impl RoundRecorder {
+    fn has_data(&self) -> bool {
+        self.last_turn.is_some()
+    }
+
+    // Same round, same file: a later report overwrites an earlier one
+    fn report_path(&self, extension: &str) -> PathBuf {
+        let started = self.started.unwrap_or_else(Local::now);
+        let tag: String = format!(
+            "{}_{}",
+            self.realm.as_deref().unwrap_or("unknown"),
+            self.team.as_deref().unwrap_or("team")
+        )
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+        PathBuf::from("logs").join(format!(
+            "{}_{}_round_report.{}",
+            started.format("%Y-%m-%d_%H-%M-%S"),
+            tag,
+            extension
+        ))
+    }
+
+    fn observe_turn(&mut self, game_state: &GameState, influence: &InfluenceMap) {
+        self.started.get_or_insert_with(Local::now);
+        self.first_turn.get_or_insert(game_state.turn_number);
+        self.last_turn = Some(game_state.turn_number);
+        self.final_score = game_state.score;
+        self.written = false;
+
+        // A raid is one stretch of turns with our units inside an enemy anthill aura, or
+        // enemy units within aura range of ours
+        let raiding = game_state
+            .my_ants
+            .values()
+            .any(|ant| influence.anthill_aura(&ant.position) == Some(AnthillAura::Hostile));
+        let raided = game_state.enemy_ants.values().any(|enemy| {
+            game_state
+                .home_tiles
+                .iter()
+                .any(|home| home.distance_to(&enemy.position) <= ANTHILL_ATTACK_RADIUS)
+        });
+        if raiding && !self.raiding {
+            self.raids_performed += 1;
+        }
+        if raided && !self.raided {
+            self.raids_suffered += 1;
+        }
+        self.raiding = raiding;
+        self.raided = raided;
+    }
+
+    fn record_death(&mut self, event: &AntDiedEvent, influence: &InfluenceMap) {
+        // Enemies are the usual killer, terrain only when nothing else could reach the hex
+        let cause = if influence.enemy_threat(&event.position) > 0.0 {
+            DeathCause::Combat
+        } else if influence.anthill_aura(&event.position) == Some(AnthillAura::Hostile) {
+            DeathCause::EnemyAnthill
+        } else if influence.acid_hazard(&event.position) > 0.0 {
+            DeathCause::Acid
+        } else {
+            DeathCause::Combat
+        };
+        *self
+            .losses
+            .entry((format!("{:?}", event.ant_type), cause))
+            .or_insert(0) += 1;
+    }
+
+    fn record_move_error(&mut self, error: &str) {
+        *self.move_errors.entry(normalize_error(error)).or_insert(0) += 1;
+    }
+
+    fn top_move_errors(&self) -> Vec<(&String, u32)> {
+        let mut errors: Vec<(&String, u32)> = self
+            .move_errors
+            .iter()
+            .map(|(error, count)| (error, *count))
+            .collect();
+        errors.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        errors.truncate(TOP_MOVE_ERRORS);
+        errors
+    }
+
+    // Share of ant-turns per strategy in windows of MIX_WINDOW turns
+    fn strategy_mix(&self) -> BTreeMap<i32, BTreeMap<&'static str, u32>> {
+        let mut mix: BTreeMap<i32, BTreeMap<&'static str, u32>> = BTreeMap::new();
+        for (turn, ants) in &self.strategy_by_turn {
+            let window = turn - turn.rem_euclid(MIX_WINDOW);
+            let counts = mix.entry(window).or_default();
+            for strategy in ants.values() {
+                *counts.entry(*strategy).or_insert(0) += 1;
+            }
+        }
+        mix
+    }
+
+    fn to_markdown(&self, end: RoundEnd, analytics: &Analytics) -> String {
+        let summary = RoundSummary::from(analytics);
+        let mut md = String::new();
+
+        let _ = writeln!(
+            md,
+            "# Round report: {} / {}\n",
+            self.realm.as_deref().unwrap_or("unknown realm"),
+            self.team.as_deref().unwrap_or("unknown team")
+        );
+        let _ = writeln!(
+            md,
+            "- Turns: {} to {}",
+            self.first_turn.unwrap_or(0),
+            self.last_turn.unwrap_or(0)
+        );
+        let _ = writeln!(md, "- Ended by: {:?}", end);
+        let _ = writeln!(md, "- Final score: {}", self.final_score);
+        let _ = writeln!(md, "- Peak ant count: {}", summary.peak_ants);
+        let _ = writeln!(
+            md,
+            "- Raids performed: {}, suffered: {}\n",
+            self.raids_performed, self.raids_suffered
+        );
+
+        md.push_str("## Calories by food type\n\n| Food | Calories |\n|---|---|\n");
+        for (food, calories) in &summary.calories {
+            let _ = writeln!(md, "| {} | {} |", food, calories);
+        }
+
+        md.push_str("\n## Units lost\n\n| Type | Cause | Count |\n|---|---|---|\n");
+        for ((ant_type, cause), count) in &self.losses {
+            let _ = writeln!(md, "| {} | {:?} | {} |", ant_type, cause, count);
+        }
+
+        md.push_str("\n## Strategy mix\n\n");
+        let mix = self.strategy_mix();
+        let mut names: Vec<&'static str> = mix
+            .values()
+            .flat_map(|counts| counts.keys().copied())
+            .collect();
+        names.sort();
+        names.dedup();
+        let _ = writeln!(md, "| Turns | {} |", names.join(" | "));
+        let _ = writeln!(md, "|---|{}", "---|".repeat(names.len()));
+        for (window, counts) in &mix {
+            let total: u32 = counts.values().sum();
+            let shares: Vec<String> = names
+                .iter()
+                .map(|name| {
+                    let count = counts.get(name).copied().unwrap_or(0);
+                    format!("{:.0}%", count as f32 * 100.0 / total.max(1) as f32)
+                })
+                .collect();
+            let _ = writeln!(
+                md,
+                "| {}-{} | {} |",
+                window,
+                window + MIX_WINDOW - 1,
+                shares.join(" | ")
+            );
+        }
+
+        md.push_str("\n## Most frequent move errors\n\n");
+        let errors = self.top_move_errors();
+        if errors.is_empty() {
+            md.push_str("None\n");
+        }
+        for (error, count) in errors {
+            let _ = writeln!(md, "- {} x {}", count, error);
+        }
+
+        md
+    }
+
+    fn to_json(&self, end: RoundEnd, analytics: &Analytics) -> serde_json::Value {
+        let summary = RoundSummary::from(analytics);
+        let losses: Vec<serde_json::Value> = self
+            .losses
+            .iter()
+            .map(|((ant_type, cause), count)| {
+                serde_json::json!({
+                    "ant_type": ant_type,
+                    "cause": format!("{:?}", cause),
+                    "count": count,
+                })
+            })
+            .collect();
+        let mix: Vec<serde_json::Value> = self
+            .strategy_mix()
+            .into_iter()
+            .map(|(window, counts)| {
+                serde_json::json!({
+                    "from_turn": window,
+                    "to_turn": window + MIX_WINDOW - 1,
+                    "ant_turns": counts,
+                })
+            })
+            .collect();
+        let errors: Vec<serde_json::Value> = self
+            .top_move_errors()
+            .into_iter()
+            .map(|(error, count)| serde_json::json!({ "error": error, "count": count }))
+            .collect();
+
+        serde_json::json!({
+            "realm": self.realm,
+            "team": self.team,
+            "first_turn": self.first_turn,
+            "last_turn": self.last_turn,
+            "ended_by": format!("{:?}", end),
+            "final_score": self.final_score,
+            "peak_ants": summary.peak_ants,
+            "calories": summary.calories,
+            "units_lost": losses,
+            "raids_performed": self.raids_performed,
+            "raids_suffered": self.raids_suffered,
+            "strategy_mix": mix,
+            "move_errors": errors,
+        })
+    }
+
+    fn write(&mut self, end: RoundEnd, analytics: &Analytics) {
+        let md_path = self.report_path("md");
+        let json_path = self.report_path("json");
+        let json = serde_json::to_string_pretty(&self.to_json(end, analytics)).unwrap_or_default();
+
+        let result = fs::create_dir_all("logs")
+            .and_then(|_| fs::write(&md_path, self.to_markdown(end, analytics)))
+            .and_then(|_| fs::write(&json_path, json));
+        match result {
+            Ok(()) => info!("Round report ({:?}) written to {}", end, md_path.display()),
+            Err(e) => warn!("Failed to write round report: {}", e),
+        }
+        self.written = true;
+    }
+}
+
+// Totals taken from the per-turn analytics
+struct RoundSummary {
+    peak_ants: usize,
+    calories: BTreeMap<String, i32>,
+}
+
+impl From<&Analytics> for RoundSummary {
+    fn from(analytics: &Analytics) -> Self {
+        let peak_ants = analytics
+            .turns()
+            .iter()
+            .map(|stats| stats.ants.values().sum::<usize>())
+            .max()
+            .unwrap_or(0);
+        let mut calories = BTreeMap::new();
+        for stats in analytics.turns() {
+            for (food_type, amount) in &stats.calories {
+                *calories.entry(format!("{:?}", food_type)).or_insert(0) += amount;
+            }
+        }
+        Self {
+            peak_ants,
+            calories,
+        }
+    }
+}
+
+// Group errors that only differ by ids and coordinates
+fn normalize_error(error: &str) -> String {
+    error
+        .split_whitespace()
+        .map(|word| {
+            let core = word.trim_matches(|c: char| !c.is_ascii_alphanumeric());
+            if core.len() >= 8 && core.chars().filter(|c| *c == '-').count() >= 2 {
+                "<id>".to_string()
+            } else if !core.is_empty() && core.chars().all(|c| c.is_ascii_digit() || c == '-') {
+                word.replace(core, "#")
+            } else {
+                word.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn round_report_system(
+    game_state: Res<GameState>,
+    influence: Res<InfluenceMap>,
+    analytics: Res<Analytics>,
+    mut recorder: ResMut<RoundRecorder>,
+    mut registrations: EventReader<ApiRegistrationEvent>,
+    mut deaths: EventReader<AntDiedEvent>,
+    mut decisions: EventReader<StrategyDecisionEvent>,
+    mut move_errors: EventReader<ApiMoveErrorsEvent>,
+    mut round_ended: EventReader<RoundEndedEvent>,
+) {
+    for event in registrations.read() {
+        recorder.realm = Some(event.0.realm.clone());
+        recorder.team = Some(event.0.name.clone());
+    }
+    for event in deaths.read() {
+        recorder.record_death(event, &influence);
+    }
+    for event in decisions.read() {
+        recorder
+            .strategy_by_turn
+            .entry(event.decision.turn_number)
+            .or_default()
+            .insert(event.ant_id.clone(), event.decision.chosen);
+    }
+    for event in move_errors.read() {
+        for error in &event.0 {
+            recorder.record_move_error(error);
+        }
+    }
+
+    for event in round_ended.read() {
+        if recorder.has_data() && !recorder.written {
+            recorder.write(event.end, &analytics);
+        }
+    }
+
+    let turn = game_state.turn_number;
+    if !game_state.connected || recorder.last_turn == Some(turn) {
+        return;
+    }
+
+    // turnNo went back: the old round was reported above and the analytics are about to reset
+    if recorder.last_turn.is_some_and(|last| turn < last) {
+        let (realm, team) = (recorder.realm.take(), recorder.team.take());
+        *recorder = RoundRecorder {
+            realm,
+            team,
+            ..Default::default()
+        };
+    }
+    recorder.observe_turn(&game_state, &influence);
+}
@@ -0,0 +1,253 @@
+use crate::influence::InfluenceMap;
+use crate::types::*;
+
+// A multi-turn route split into per-turn legs. Each leg fits the ant's movement points and
+// ends on a hex that does not hurt to stand on, whenever such a hex is in reach
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Route {
+    legs: Vec<Vec<HexCoord>>,
+    // End-of-turn damage at each leg's stop when the route was planned
+    stop_damage: Vec<f32>,
+    // Movement points to walk the whole route
+    cost: i32,
+}
+
+impl Route {
+    /// Split a full path (start excluded) into legs of at most `movement_points` MP each
+    pub fn segment(
+        path: &[HexCoord],
+        movement_points: i32,
+        game_state: &GameState,
+        influence: &InfluenceMap,
+    ) -> Self {
+        let mut legs = Vec::new();
+        let mut stop_damage = Vec::new();
+        let mut start = 0;
+        let cost = path
+            .iter()
+            .map(|pos| Self::step_cost(pos, game_state))
+            .sum();
+
+        while start < path.len() {
+            // Hexes reachable this turn and the cost to get there
+            let mut cost = 0;
+            let mut reachable = 0;
+            for pos in &path[start..] {
+                cost += Self::step_cost(pos, game_state);
+                if cost > movement_points {
+                    break;
+                }
+                reachable += 1;
+            }
+
+            // Always make progress, even across a hex costing more than a full turn
+            if reachable == 0 {
+                reachable = 1;
+            }
+
+            // Furthest safe stop, otherwise the furthest one that hurts least
+            let end = (start..start + reachable)
+                .max_by(|a, b| {
+                    let damage_a = influence.end_of_turn_damage(&path[*a]);
+                    let damage_b = influence.end_of_turn_damage(&path[*b]);
+                    damage_b
+                        .partial_cmp(&damage_a)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                        .then(a.cmp(b))
+                })
+                .unwrap_or(start);
+
+            legs.push(path[start..=end].to_vec());
+            stop_damage.push(influence.end_of_turn_damage(&path[end]));
+            start = end + 1;
+        }
+
+        Self {
+            legs,
+            stop_damage,
+            cost,
+        }
+    }
+
+    // Movement points spent entering a hex
+    pub fn step_cost(pos: &HexCoord, game_state: &GameState) -> i32 {
+        game_state
+            .visible_tiles
+            .get(pos)
+            .and_then(|tile| tile.tile_type.movement_cost())
+            .unwrap_or(1) // Assume unexplored tiles are plain
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.legs.is_empty()
+    }
+
+    // Moves for this turn
+    pub fn next_leg(&self) -> Vec<HexCoord> {
+        self.legs.first().cloned().unwrap_or_default()
+    }
+
+    pub fn next_hex(&self) -> Option<&HexCoord> {
+        self.legs.first().and_then(|leg| leg.first())
+    }
+
+    pub fn destination(&self) -> Option<&HexCoord> {
+        self.legs.last().and_then(|leg| leg.last())
+    }
+
+    // Arrival time in turns
+    pub fn eta(&self) -> usize {
+        self.legs.len()
+    }
+
+    pub fn hexes(&self) -> impl Iterator<Item = &HexCoord> {
+        self.legs.iter().flatten()
+    }
+
+    // Hexes past this turn's leg
+    pub fn later_legs(&self) -> impl Iterator<Item = &HexCoord> {
+        self.legs.iter().skip(1).flatten()
+    }
+
+    // Movement points when planned, hexes walked since are not subtracted
+    pub fn cost(&self) -> i32 {
+        self.cost
+    }
+
+    pub fn len(&self) -> usize {
+        self.legs.iter().map(Vec::len).sum()
+    }
+
+    // Drop everything up to the ant's position. Returns false if the ant is not on the route
+    pub fn advance_to(&mut self, position: HexCoord) -> bool {
+        let found = self.legs.iter().enumerate().find_map(|(leg_index, leg)| {
+            leg.iter()
+                .position(|pos| *pos == position)
+                .map(|index| (leg_index, index))
+        });
+
+        let Some((leg_index, index)) = found else {
+            return false;
+        };
+
+        self.legs.drain(..leg_index);
+        self.stop_damage.drain(..leg_index);
+        self.legs[0].drain(..=index);
+        if self.legs[0].is_empty() {
+            self.legs.remove(0);
+            self.stop_damage.remove(0);
+        }
+        true
+    }
+
+    // The world changed somewhere along the remaining route since it was planned
+    pub fn is_stale(&self, game_state: &GameState, influence: &InfluenceMap) -> bool {
+        let blocked = self.hexes().any(|pos| {
+            influence.is_learned_obstacle(pos)
+                || game_state
+                    .visible_tiles
+                    .get(pos)
+                    .is_some_and(|tile| !tile.tile_type.is_passable())
+        });
+        // e.g. an enemy anthill spotted next to a stop that looked safe
+        let unsafe_stop = self
+            .legs
+            .iter()
+            .zip(&self.stop_damage)
+            .filter_map(|(leg, damage)| leg.last().map(|pos| (pos, *damage)))
+            .any(|(pos, damage)| influence.end_of_turn_damage(pos) > damage);
+
+        blocked || unsafe_stop
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(length: i32) -> Vec<HexCoord> {
+        (1..=length).map(|q| HexCoord::new(q, 0)).collect()
+    }
+
+    fn world(tiles: &[(HexCoord, TileType)]) -> (GameState, InfluenceMap) {
+        let mut game_state = GameState::default();
+        for (position, tile_type) in tiles {
+            game_state.visible_tiles.insert(
+                *position,
+                Tile {
+                    position: *position,
+                    tile_type: *tile_type,
+                    cost: 1,
+                },
+            );
+        }
+        let mut influence = InfluenceMap::default();
+        influence.recompute(&game_state);
+        (game_state, influence)
+    }
+
+    #[test]
+    fn segment_splits_legs_by_dirt_cost() {
+        let path = line(4);
+        let (game_state, influence) = world(&[(path[1], TileType::Dirt)]);
+
+        let route = Route::segment(&path, 3, &game_state, &influence);
+
+        assert_eq!(route.next_leg(), vec![path[0], path[1]]);
+        assert_eq!(route.eta(), 2);
+        assert_eq!(route.destination(), Some(&path[3]));
+        assert_eq!(route.cost(), 5);
+    }
+
+    #[test]
+    fn segment_makes_progress_across_hex_costing_more_than_a_turn() {
+        let path = line(2);
+        let (game_state, influence) = world(&[(path[0], TileType::Dirt)]);
+
+        let route = Route::segment(&path, 1, &game_state, &influence);
+
+        assert_eq!(route.next_leg(), vec![path[0]]);
+        assert_eq!(route.eta(), 2);
+    }
+
+    #[test]
+    fn segment_stops_short_of_acid() {
+        let path = line(4);
+        let (game_state, influence) = world(&[(path[2], TileType::Acid)]);
+
+        let route = Route::segment(&path, 3, &game_state, &influence);
+
+        assert_eq!(route.next_leg(), vec![path[0], path[1]]);
+        assert_eq!(
+            route.later_legs().copied().collect::<Vec<_>>(),
+            vec![path[2], path[3]]
+        );
+    }
+
+    #[test]
+    fn advance_to_off_route_keeps_route() {
+        let path = line(4);
+        let (game_state, influence) = world(&[]);
+        let mut route = Route::segment(&path, 2, &game_state, &influence);
+        let before = route.clone();
+
+        assert!(!route.advance_to(HexCoord::new(0, 5)));
+        assert_eq!(route, before);
+    }
+
+    #[test]
+    fn advance_to_mid_leg_drops_walked_hexes() {
+        let path = line(4);
+        let (game_state, influence) = world(&[]);
+        let mut route = Route::segment(&path, 2, &game_state, &influence);
+
+        assert!(route.advance_to(path[0]));
+        assert_eq!(route.next_leg(), vec![path[1]]);
+        assert_eq!(route.eta(), 2);
+
+        // Reaching the end of a leg moves on to the next one
+        assert!(route.advance_to(path[1]));
+        assert_eq!(route.next_leg(), vec![path[2], path[3]]);
+        assert_eq!(route.eta(), 1);
+    }
+}
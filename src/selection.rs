@@ -0,0 +1,298 @@
+use crate::hex_utils::HexGeometry;
+use crate::input::CameraMouseControl;
+use crate::menu::MenuState;
+use crate::types::*;
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+
+// Screen distance the cursor has to travel before a click becomes a box
+const BOX_SELECT_THRESHOLD: f32 = 6.0;
+// Ant models are tiny scenes, so they are picked against a sphere around their origin
+const ANT_PICK_RADIUS: f32 = 0.35;
+const RING_RADIUS: f32 = 0.4;
+
+// What the cursor is over and what was clicked in the 3D view
+#[derive(Resource, Default)]
+pub struct Selection {
+    pub hovered_hex: Option<HexCoord>,
+    pub hovered_ant: Option<Entity>,
+    pub hex: Option<HexCoord>,
+    pub ants: Vec<Entity>,
+    cursor: Option<Vec2>,
+    drag_start: Option<Vec2>,
+}
+
+impl Selection {
+    // Screen rectangle of a box selection in progress
+    pub fn drag_rect(&self) -> Option<Rect> {
+        let start = self.drag_start?;
+        let cursor = self.cursor?;
+        (start.distance(cursor) >= BOX_SELECT_THRESHOLD).then(|| Rect::from_corners(start, cursor))
+    }
+
+    pub fn clear(&mut self) {
+        self.hex = None;
+        self.ants.clear();
+    }
+
+    fn toggle_ant(&mut self, entity: Entity) {
+        match self.ants.iter().position(|selected| *selected == entity) {
+            Some(index) => {
+                self.ants.remove(index);
+            }
+            None => self.ants.push(entity),
+        }
+    }
+}
+
+// Distance along the ray to the sphere, if the ray hits it
+fn ray_sphere_distance(ray: Ray3d, center: Vec3, radius: f32) -> Option<f32> {
+    let to_center = center - ray.origin;
+    let along = to_center.dot(*ray.direction);
+    if along < 0.0 {
+        return None;
+    }
+    let miss_squared = to_center.length_squared() - along * along;
+    (miss_squared <= radius * radius).then(|| along - (radius * radius - miss_squared).sqrt())
+}
+
+pub fn pick_under_cursor(
+    mut contexts: EguiContexts,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    ants: Query<(Entity, &GlobalTransform, &AntMarker)>,
+    menu_state: Res<MenuState>,
+    mouse_control: Res<CameraMouseControl>,
+    mut selection: ResMut<Selection>,
+    mut selected_ant: ResMut<SelectedAnt>,
+) -> Result {
+    // Ants that died since they were selected
+    selection.ants.retain(|entity| ants.contains(*entity));
+
+    let ctx = contexts.ctx_mut()?;
+    let window = windows.single()?;
+    let (camera, camera_transform) = cameras.single()?;
+
+    selection.cursor = window.cursor_position();
+    let ray = selection
+        .cursor
+        .and_then(|cursor| camera.viewport_to_world(camera_transform, cursor).ok());
+    let blocked = menu_state.show_menu || mouse_control.enabled || ctx.is_pointer_over_area();
+    let (Some(cursor), Some(ray), false) = (selection.cursor, ray, blocked) else {
+        selection.hovered_hex = None;
+        selection.hovered_ant = None;
+        selection.drag_start = None;
+        return Ok(());
+    };
+
+    selection.hovered_hex = ray
+        .intersect_plane(Vec3::ZERO, InfinitePlane3d::new(Vec3::Y))
+        .map(|distance| world_pos_to_hex(&ray.get_point(distance)));
+    selection.hovered_ant = ants
+        .iter()
+        .filter_map(|(entity, transform, _)| {
+            ray_sphere_distance(ray, transform.translation(), ANT_PICK_RADIUS)
+                .map(|distance| (entity, distance))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity);
+
+    if mouse_button_input.just_pressed(MouseButton::Left) {
+        selection.drag_start = Some(cursor);
+    }
+    if !mouse_button_input.just_released(MouseButton::Left) || selection.drag_start.is_none() {
+        return Ok(());
+    }
+
+    // Shift adds to the selection instead of replacing it
+    let additive =
+        keyboard_input.pressed(KeyCode::ShiftLeft) || keyboard_input.pressed(KeyCode::ShiftRight);
+    let rect = selection.drag_rect();
+    selection.drag_start = None;
+    if !additive {
+        selection.clear();
+    }
+
+    match (rect, selection.hovered_ant) {
+        // Box selection only picks our own ants
+        (Some(rect), _) => {
+            for (entity, transform, marker) in &ants {
+                let inside = camera
+                    .world_to_viewport(camera_transform, transform.translation())
+                    .is_ok_and(|pos| rect.contains(pos));
+                if inside && !marker.is_enemy && !selection.ants.contains(&entity) {
+                    selection.ants.push(entity);
+                }
+            }
+        }
+        (None, Some(entity)) => selection.toggle_ant(entity),
+        (None, None) => selection.hex = selection.hovered_hex,
+    }
+
+    // Keep the inspector on the last of our ants that was picked
+    let picked = selection
+        .ants
+        .iter()
+        .rev()
+        .filter_map(|entity| ants.get(*entity).ok())
+        .find(|(_, _, marker)| !marker.is_enemy);
+    if let Some((_, _, marker)) = picked {
+        selected_ant.ant_id = Some(marker.ant_id.clone());
+    }
+
+    Ok(())
+}
+
+fn draw_hex_outline(gizmos: &mut Gizmos, hex: HexCoord, color: Color) {
+    let center = hex_to_world_pos(&hex) + Vec3::Y * 0.02;
+    let corners = HexGeometry::hex_corners(center);
+    for i in 0..6 {
+        gizmos.line(corners[i], corners[(i + 1) % 6], color);
+    }
+}
+
+pub fn draw_selection_highlights(
+    mut gizmos: Gizmos,
+    selection: Res<Selection>,
+    ants: Query<&GlobalTransform, With<AntMarker>>,
+) {
+    let flat = Isometry3d::from_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2));
+    let ring = |gizmos: &mut Gizmos, entity: Entity, color: Color| {
+        if let Ok(transform) = ants.get(entity) {
+            let center = transform.translation().with_y(0.05);
+            gizmos.circle(
+                Isometry3d::from_translation(center) * flat,
+                RING_RADIUS,
+                color,
+            );
+        }
+    };
+
+    for entity in &selection.ants {
+        ring(&mut gizmos, *entity, Color::srgb(1.0, 0.9, 0.2));
+    }
+    if let Some(entity) = selection.hovered_ant {
+        ring(&mut gizmos, entity, Color::srgba(1.0, 1.0, 1.0, 0.6));
+    }
+
+    if let Some(hex) = selection.hex {
+        draw_hex_outline(&mut gizmos, hex, Color::srgb(1.0, 0.9, 0.2));
+    }
+    if let Some(hex) = selection.hovered_hex {
+        draw_hex_outline(&mut gizmos, hex, Color::srgba(1.0, 1.0, 1.0, 0.5));
+    }
+}
+
+fn hex_summary(ui: &mut egui::Ui, hex: HexCoord, game_state: &GameState) {
+    ui.strong(format!("Hex ({}, {})", hex.q, hex.r));
+
+    match game_state.visible_tiles.get(&hex) {
+        Some(tile) => {
+            ui.label(format!("Tile: {:?}", tile.tile_type));
+            match tile.tile_type.movement_cost() {
+                Some(_) => ui.label(format!("Cost: {}", tile.cost)),
+                None => ui.label("Cost: impassable"),
+            };
+        }
+        None => {
+            ui.weak("Not visible");
+        }
+    }
+
+    if hex == game_state.main_spot {
+        ui.label("Our anthill (main spot)");
+    } else if game_state.home_tiles.contains(&hex) {
+        ui.label("Our anthill");
+    }
+
+    if let Some(food) = game_state.food_on_map.get(&hex) {
+        ui.label(format!("Food: {} {:?}", food.amount, food.food_type));
+    }
+
+    let mut my_ants: Vec<&Ant> = game_state
+        .my_ants
+        .values()
+        .filter(|ant| ant.position == hex)
+        .collect();
+    my_ants.sort_by(|a, b| a.id.cmp(&b.id));
+    let enemies: Vec<&Enemy> = game_state
+        .enemy_ants
+        .values()
+        .filter(|enemy| enemy.position == hex)
+        .collect();
+    if my_ants.is_empty() && enemies.is_empty() {
+        return;
+    }
+
+    ui.separator();
+    for ant in my_ants {
+        let short = &ant.id[..ant.id.len().min(8)];
+        let cargo = ant
+            .food()
+            .map(|(food_type, amount)| format!(", carrying {} {:?}", amount, food_type))
+            .unwrap_or_default();
+        ui.label(format!(
+            "{} {:?} {}/{} HP{}",
+            short, ant.ant_type, ant.health, ant.max_health, cargo
+        ));
+    }
+    for enemy in enemies {
+        ui.colored_label(
+            egui::Color32::LIGHT_RED,
+            format!(
+                "Enemy {:?} {}/{} HP",
+                enemy.ant_type,
+                enemy.health,
+                enemy.ant_type.health()
+            ),
+        );
+    }
+}
+
+pub fn selection_overlay_system(
+    mut contexts: EguiContexts,
+    selection: Res<Selection>,
+    game_state: Res<GameState>,
+) -> Result {
+    let ctx = contexts.ctx_mut()?;
+
+    if let Some(rect) = selection.drag_rect() {
+        let painter = ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Foreground,
+            egui::Id::new("selection_box"),
+        ));
+        let rect = egui::Rect::from_min_max(
+            egui::pos2(rect.min.x, rect.min.y),
+            egui::pos2(rect.max.x, rect.max.y),
+        );
+        painter.rect_filled(
+            rect,
+            0.0,
+            egui::Color32::from_rgba_unmultiplied(255, 230, 50, 30),
+        );
+        painter.rect_stroke(
+            rect,
+            0.0,
+            egui::Stroke::new(1.0, egui::Color32::YELLOW),
+            egui::StrokeKind::Inside,
+        );
+        return Ok(());
+    }
+
+    let (Some(hex), Some(pointer)) = (selection.hovered_hex, ctx.pointer_hover_pos()) else {
+        return Ok(());
+    };
+    egui::Area::new(egui::Id::new("hover_tooltip"))
+        .order(egui::Order::Tooltip)
+        .fixed_pos(pointer + egui::vec2(16.0, 16.0))
+        .interactable(false)
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                hex_summary(ui, hex, &game_state);
+            });
+        });
+
+    Ok(())
+}
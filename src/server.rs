@@ -25,6 +25,8 @@ pub struct ServerTicker {
     pub waiting_for_lobby: bool,
     pub lobby_wait_timer: Timer,
     pub registration_backoff: f32,
+    pub logs_timer: Timer,
+    pub logs_pending: bool, // Poll due but not sent yet, e.g. held back by the rate limiter
 }
 
 impl ServerClient {
@@ -208,6 +210,7 @@ pub fn handle_game_move_commands(
     mut commands: Commands,
     mut move_command_events: EventReader<MoveCommandEvent>,
     server_client: Res<ServerClient>,
+    mut rate_limiter: ResMut<RateLimiter>,
     tokio_tasks: Res<TokioTasksRuntime>,
 ) {
     if move_command_events.is_empty() {
@@ -241,6 +244,7 @@ pub fn handle_game_move_commands(
         };
         info!(target: "server", "Sending {} move commands directly to server", api_commands.len());
 
+        rate_limiter.record_request();
         spawn_server_task(&mut commands, &tokio_tasks, move |_ctx| async move {
             client.send_moves(&move_request).await
         });
@@ -295,6 +299,8 @@ pub fn setup_server_client(mut commands: Commands, config: Res<ServerConfig>) {
         waiting_for_lobby: false,
         lobby_wait_timer,
         registration_backoff: 2.0, // start with 2 seconds
+        logs_timer: Timer::new(config.logs_poll_rate, TimerMode::Repeating),
+        logs_pending: false,
     });
 
     info!(target: "server", "Server client initialized with URL: {}", config.url);
@@ -304,6 +310,7 @@ pub fn server_tick_system(
     mut commands: Commands,
     mut server_ticker: ResMut<ServerTicker>,
     server_client: Res<ServerClient>,
+    mut rate_limiter: ResMut<RateLimiter>,
     time: Res<Time>,
     tokio_tasks: Res<TokioTasksRuntime>,
 ) {
@@ -336,6 +343,7 @@ pub fn server_tick_system(
     server_ticker.timer.tick(time.delta());
     if server_ticker.timer.just_finished() && server_client.registered {
         info!(target: "server", "Requesting arena state (registered: {})", server_client.registered);
+        rate_limiter.record_request();
 
         let client = ServerClient {
             client: server_client.client.clone(),
@@ -516,6 +524,7 @@ pub fn handle_move_response_tasks(
     mut commands: Commands,
     mut game_state: ResMut<GameState>,
     mut arena_events: EventWriter<ApiArenaEvent>,
+    mut move_error_events: EventWriter<ApiMoveErrorsEvent>,
     mut query: Query<(Entity, &mut ServerTask<Result<ApiMoveResponse>>)>,
 ) {
     for (entity, mut task) in &mut query {
@@ -544,6 +553,7 @@ pub fn handle_move_response_tasks(
 
                     if !move_response.errors.is_empty() {
                         warn!(target: "server", "Move errors: {:?}", move_response.errors);
+                        move_error_events.write(ApiMoveErrorsEvent(move_response.errors));
                     }
 
                     info!(target: "server", "Move response processed successfully");
@@ -561,13 +571,24 @@ pub fn handle_move_response_tasks(
     }
 }
 
+// Logs are polled periodically and on the L key. A poll the request budget holds back stays
+// due and goes out as soon as the budget allows
 pub fn handle_logs_requests(
     mut commands: Commands,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     server_client: Res<ServerClient>,
+    mut server_ticker: ResMut<ServerTicker>,
+    mut rate_limiter: ResMut<RateLimiter>,
+    time: Res<Time>,
     tokio_tasks: Res<TokioTasksRuntime>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::KeyL) && server_client.registered {
+    server_ticker.logs_timer.tick(time.delta());
+    if keyboard_input.just_pressed(KeyCode::KeyL) || server_ticker.logs_timer.just_finished() {
+        server_ticker.logs_pending = true;
+    }
+
+    if server_ticker.logs_pending && server_client.registered && rate_limiter.can_make_request() {
+        server_ticker.logs_pending = false;
         // Clone the entire client with its registration state
         let client = ServerClient {
             client: server_client.client.clone(),
@@ -580,12 +601,13 @@ pub fn handle_logs_requests(
             client.get_logs().await
         });
 
-        info!(target: "server", "Requesting game logs");
+        debug!(target: "server", "Requesting game logs");
     }
 }
 
 pub fn handle_logs_response_tasks(
     mut commands: Commands,
+    mut logs_events: EventWriter<ApiLogsEvent>,
     mut query: Query<(Entity, &mut ServerTask<Result<Vec<ApiLogMessage>>>)>,
 ) {
     for (entity, mut task) in &mut query {
@@ -596,10 +618,8 @@ pub fn handle_logs_response_tasks(
         if let Some(handle) = task.take_handle() {
             match futures::executor::block_on(handle) {
                 Ok(Ok(logs)) => {
-                    info!(target: "server", "Received {} log messages", logs.len());
-                    for log in logs {
-                        info!(target: "server", "[{}] {}", log.time, log.message);
-                    }
+                    debug!(target: "server", "Received {} log messages", logs.len());
+                    logs_events.write(ApiLogsEvent(logs));
                 }
                 Ok(Err(e)) => {
                     error!(target: "server", "Failed to fetch logs: {}", e);
@@ -742,6 +762,11 @@ impl Default for RateLimiter {
 }
 
 impl RateLimiter {
+    // Requests that must go out regardless still count against the budget
+    pub fn record_request(&mut self) {
+        self.last_request_time = std::time::Instant::now();
+    }
+
     pub fn can_make_request(&mut self) -> bool {
         let now = std::time::Instant::now();
         if now.duration_since(self.last_request_time) >= self.min_interval {
@@ -0,0 +1,344 @@
+use crate::influence::{AnthillAura, InfluenceMap};
+use crate::types::*;
+use std::collections::{HashMap, HashSet};
+
+// Soldier squads for AttackStrategy. A squad picks one enemy, surrounds it on a contiguous
+// arc of neighbouring hexes so every member is adjacent to another member and the target
+// (the 50% support bonus), and waits until it is assembled before engaging
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SquadOrder {
+    // Hold near this hex until the rest of the squad catches up
+    Rally(HexCoord),
+    // End the turn on `stand`, next to the enemy on `target`
+    Engage {
+        target: HexCoord,
+        stand: HexCoord,
+        finishing: bool, // Our combined attacks this turn should kill the target
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct Squad {
+    pub members: Vec<String>,
+    pub target: Option<HexCoord>,
+    pub assembled: bool,
+    pub expected_damage: f32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SquadPlanner {
+    squads: Vec<Squad>,
+    orders: HashMap<String, SquadOrder>,
+}
+
+struct Engagement {
+    stands: Vec<(String, HexCoord)>,
+    damage: f32, // Bonus-adjusted damage from members that reach their stand this turn
+    all_reach: bool,
+}
+
+impl SquadPlanner {
+    pub fn order(&self, ant_id: &str) -> Option<SquadOrder> {
+        self.orders.get(ant_id).copied()
+    }
+
+    pub fn squads(&self) -> &[Squad] {
+        &self.squads
+    }
+
+    pub fn replan(
+        &mut self,
+        game_state: &GameState,
+        influence: &InfluenceMap,
+        squad_size: usize,
+        assembly_radius: i32,
+    ) {
+        self.orders.clear();
+        self.regroup(game_state, squad_size.max(2));
+
+        let mut squads = std::mem::take(&mut self.squads);
+        for squad in &mut squads {
+            self.plan_squad(squad, game_state, influence, assembly_radius);
+        }
+        self.squads = squads;
+    }
+
+    // Keep squads stable across turns, refill them and group leftover soldiers
+    fn regroup(&mut self, game_state: &GameState, squad_size: usize) {
+        let soldiers: HashMap<&String, &Ant> = game_state
+            .my_ants
+            .iter()
+            .filter(|(_, ant)| ant.ant_type == AntType::Soldier)
+            .collect();
+
+        for squad in &mut self.squads {
+            squad.members.retain(|id| soldiers.contains_key(id));
+            squad.members.truncate(squad_size);
+        }
+        self.squads.retain(|squad| squad.members.len() >= 2);
+
+        let assigned: HashSet<&String> = self
+            .squads
+            .iter()
+            .flat_map(|squad| squad.members.iter())
+            .collect();
+        let mut free: Vec<&Ant> = soldiers
+            .values()
+            .filter(|ant| !assigned.contains(&ant.id))
+            .copied()
+            .collect();
+        free.sort_by(|a, b| b.id.cmp(&a.id));
+
+        for squad in &mut self.squads {
+            let anchor = soldiers[&squad.members[0]].position;
+            while squad.members.len() < squad_size {
+                let Some(index) = Self::nearest(&free, anchor) else {
+                    break;
+                };
+                squad.members.push(free.remove(index).id.clone());
+            }
+        }
+
+        while let Some(leader) = free.pop() {
+            let mut members = vec![leader.id.clone()];
+            while members.len() < squad_size {
+                let Some(index) = Self::nearest(&free, leader.position) else {
+                    break;
+                };
+                members.push(free.remove(index).id.clone());
+            }
+
+            // A lone soldier fights on its own
+            if members.len() >= 2 {
+                self.squads.push(Squad {
+                    members,
+                    target: None,
+                    assembled: false,
+                    expected_damage: 0.0,
+                });
+            }
+        }
+    }
+
+    fn nearest(ants: &[&Ant], pos: HexCoord) -> Option<usize> {
+        ants.iter()
+            .enumerate()
+            .min_by_key(|(_, ant)| ant.position.distance_to(&pos))
+            .map(|(index, _)| index)
+    }
+
+    fn plan_squad(
+        &mut self,
+        squad: &mut Squad,
+        game_state: &GameState,
+        influence: &InfluenceMap,
+        assembly_radius: i32,
+    ) {
+        let members: Vec<&Ant> = squad
+            .members
+            .iter()
+            .filter_map(|id| game_state.my_ants.get(id))
+            .collect();
+        squad.target = None;
+        squad.assembled = false;
+        squad.expected_damage = 0.0;
+
+        // Focus the enemy we can finish, otherwise the weakest one close by
+        let mut best: Option<(f32, &Enemy, Engagement)> = None;
+        for enemy in game_state.enemy_ants.values() {
+            let Some(engagement) = Self::plan_engagement(&members, enemy, game_state, influence)
+            else {
+                continue;
+            };
+
+            let distance = members
+                .iter()
+                .map(|ant| ant.position.distance_to(&enemy.position))
+                .sum::<i32>() as f32
+                / members.len() as f32;
+            let hp_ratio = enemy.health as f32 / enemy.ant_type.health().max(1) as f32;
+            let kill_bonus = if engagement.damage >= enemy.health as f32 {
+                100.0
+            } else {
+                0.0
+            };
+            let score = kill_bonus + (1.0 - hp_ratio) * 20.0 - distance * 2.0;
+
+            if best
+                .as_ref()
+                .is_none_or(|(best_score, _, _)| score > *best_score)
+            {
+                best = Some((score, enemy, engagement));
+            }
+        }
+
+        // The member closest to the action is where the others gather
+        let vanguard = match &best {
+            Some((_, enemy, _)) => members
+                .iter()
+                .min_by_key(|ant| ant.position.distance_to(&enemy.position)),
+            None => members.first(),
+        };
+        let Some(vanguard) = vanguard.copied() else {
+            return;
+        };
+        squad.assembled = members
+            .iter()
+            .all(|ant| ant.position.distance_to(&vanguard.position) <= assembly_radius);
+
+        let Some((_, enemy, engagement)) = best else {
+            // Nothing to fight, only pull stragglers back together
+            if !squad.assembled {
+                for ant in &members {
+                    self.orders
+                        .insert(ant.id.clone(), SquadOrder::Rally(vanguard.position));
+                }
+            }
+            return;
+        };
+
+        squad.target = Some(enemy.position);
+        squad.expected_damage = engagement.damage;
+
+        let finishing = engagement.damage >= enemy.health as f32;
+        let in_contact = members
+            .iter()
+            .any(|ant| enemy.position.neighbors().contains(&ant.position));
+
+        if squad.assembled || engagement.all_reach || in_contact || finishing {
+            for (ant_id, stand) in engagement.stands {
+                self.orders.insert(
+                    ant_id,
+                    SquadOrder::Engage {
+                        target: enemy.position,
+                        stand,
+                        finishing,
+                    },
+                );
+            }
+        } else {
+            for ant in &members {
+                self.orders
+                    .insert(ant.id.clone(), SquadOrder::Rally(vanguard.position));
+            }
+        }
+    }
+
+    // Best contiguous arc of hexes around the enemy for the squad to stand on
+    fn plan_engagement(
+        members: &[&Ant],
+        enemy: &Enemy,
+        game_state: &GameState,
+        influence: &InfluenceMap,
+    ) -> Option<Engagement> {
+        // Neighbours come back in ring order, so consecutive entries touch each other
+        let ring = enemy.position.neighbors();
+        let member_ids: HashSet<&String> = members.iter().map(|ant| &ant.id).collect();
+        let usable: Vec<bool> = ring
+            .iter()
+            .map(|pos| {
+                let passable = game_state
+                    .visible_tiles
+                    .get(pos)
+                    .is_some_and(|tile| tile.tile_type.is_passable());
+                let enemy_there = game_state
+                    .enemy_ants
+                    .values()
+                    .any(|other| other.position == *pos);
+                let soldier_there = game_state.my_ants.values().any(|other| {
+                    other.position == *pos
+                        && other.ant_type == AntType::Soldier
+                        && !member_ids.contains(&other.id)
+                });
+                passable && !enemy_there && !soldier_there
+            })
+            .collect();
+
+        let ring_len = ring.len();
+        for arc_len in (1..=members.len().min(ring_len)).rev() {
+            let mut best: Option<(f32, Vec<(String, HexCoord)>)> = None;
+
+            for start in 0..ring_len {
+                let arc: Vec<HexCoord> = (0..arc_len)
+                    .map(|offset| (start + offset) % ring_len)
+                    .filter(|index| usable[*index])
+                    .map(|index| ring[index])
+                    .collect();
+                if arc.len() < arc_len {
+                    continue;
+                }
+
+                // Greedy assignment, shortest walks first
+                let mut pairs: Vec<(i32, &Ant, HexCoord)> = members
+                    .iter()
+                    .flat_map(|ant| {
+                        arc.iter()
+                            .map(move |pos| (ant.position.distance_to(pos), *ant, *pos))
+                    })
+                    .collect();
+                pairs.sort_by_key(|(distance, ant, _)| (*distance, ant.id.clone()));
+
+                let mut used_ants: HashSet<&String> = HashSet::new();
+                let mut used_hexes: HashSet<HexCoord> = HashSet::new();
+                let mut stands = Vec::new();
+                let mut cost = 0.0;
+                for (distance, ant, pos) in pairs {
+                    if used_ants.contains(&ant.id) || used_hexes.contains(&pos) {
+                        continue;
+                    }
+                    used_ants.insert(&ant.id);
+                    used_hexes.insert(pos);
+                    cost += distance as f32 + influence.end_of_turn_damage(&pos) / 10.0;
+                    stands.push((ant.id.clone(), pos));
+                }
+
+                if best.as_ref().is_none_or(|(best_cost, _)| cost < *best_cost) {
+                    best = Some((cost, stands));
+                }
+            }
+
+            if let Some((_, stands)) = best {
+                return Some(Self::engagement(members, stands, influence));
+            }
+        }
+
+        None
+    }
+
+    fn engagement(
+        members: &[&Ant],
+        stands: Vec<(String, HexCoord)>,
+        influence: &InfluenceMap,
+    ) -> Engagement {
+        let reaching: Vec<(&Ant, HexCoord)> = stands
+            .iter()
+            .filter_map(|(id, stand)| {
+                let ant = members.iter().find(|ant| ant.id == *id)?;
+                (ant.position.distance_to(stand) <= ant.ant_type.speed()).then_some((*ant, *stand))
+            })
+            .collect();
+
+        // On a contiguous arc every member has a partner next to both itself and the target
+        let supported = reaching.len() >= 2;
+        let damage = reaching
+            .iter()
+            .map(|(ant, stand)| {
+                let mut multiplier = 1.0;
+                if supported {
+                    multiplier += SUPPORT_BONUS;
+                }
+                if influence.anthill_aura(stand) == Some(AnthillAura::Friendly) {
+                    multiplier += ANTHILL_BONUS;
+                }
+                ant.ant_type.attack() as f32 * multiplier
+            })
+            .sum();
+
+        Engagement {
+            all_reach: reaching.len() == members.len(),
+            stands,
+            damage,
+        }
+    }
+}
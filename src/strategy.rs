@@ -1,7 +1,11 @@
+use crate::config::*;
+use crate::influence::InfluenceMap;
+use crate::route::Route;
+use crate::squad::{SquadOrder, SquadPlanner};
 use crate::types::*;
 use crate::utils::*;
 use bevy::prelude::*;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 // Strategy trait that all strategies must implement
 pub trait Strategy {
@@ -9,78 +13,555 @@ pub trait Strategy {
     fn base_priority(&self, ant_type: AntType) -> f32;
 
     // Calculate global priority modifiers based on game state
-    fn global_priority_modifier(&self, game_state: &GameState) -> f32;
+    fn global_priority_modifier(&self, game_state: &GameState, influence: &InfluenceMap) -> f32;
 
     // Calculate individual priority modifiers based on ant's state
-    fn individual_priority_modifier(&self, ant: &Ant, game_state: &GameState) -> f32;
+    fn individual_priority_modifier(
+        &self,
+        ant: &Ant,
+        game_state: &GameState,
+        influence: &InfluenceMap,
+    ) -> f32;
+
+    // Pick up new weights from the strategy config
+    fn configure(&mut self, _config: &StrategyConfig) {}
+
+    // Called once per planning pass before any ant is scored, for state shared between ants
+    fn prepare(&mut self, _game_state: &GameState, _influence: &InfluenceMap) {}
+
+    // Where the ant should head, None to stay put. The route there is planned by the caller
+    fn goal(&self, ant: &Ant, game_state: &GameState, influence: &InfluenceMap)
+    -> Option<PlanGoal>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanGoal {
+    Food(HexCoord),
+    Home(HexCoord),
+    Defend(HexCoord),
+    Explore(HexCoord),
+    Attack(HexCoord),
+}
+
+impl PlanGoal {
+    pub fn position(&self) -> HexCoord {
+        match self {
+            PlanGoal::Food(pos)
+            | PlanGoal::Home(pos)
+            | PlanGoal::Defend(pos)
+            | PlanGoal::Explore(pos)
+            | PlanGoal::Attack(pos) => *pos,
+        }
+    }
+
+    // Frontier and attack targets move every turn and are picked again, the others are
+    // followed across turns
+    pub fn is_persistent(&self) -> bool {
+        !matches!(self, PlanGoal::Explore(_) | PlanGoal::Attack(_))
+    }
+}
+
+// Per-ant plan memory
+#[derive(Debug, Clone)]
+pub struct AntPlan {
+    pub strategy: &'static str,
+    pub goal: Option<PlanGoal>,
+    pub route: Route,
+    pub committed_until: i32,
+    pub started_turn: i32,
+    pub last_turn: i32,
+    pub last_position: HexCoord,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanInvalidation {
+    GoalReached,
+    TargetFoodGone,
+    PathBlocked,
+    RouteChanged,
+    LowHealth,
+    AntDied,
+}
 
-    // Execute the strategy for a specific ant
-    fn execute(&self, ant: &Ant, game_state: &GameState) -> Vec<HexCoord>;
+// Score breakdown of one strategy for one ant
+#[derive(Debug, Clone)]
+pub struct StrategyScore {
+    pub strategy: &'static str,
+    pub base: f32,
+    pub global: f32,
+    pub individual: f32,
+    pub hysteresis: f32,
+    pub total: f32,
+}
+
+// What the manager decided for an ant on a given turn and why
+#[derive(Debug, Clone)]
+pub struct StrategyDecision {
+    pub turn_number: i32,
+    pub chosen: &'static str,
+    pub committed: bool, // Chosen because of plan commitment rather than score
+    pub scores: Vec<StrategyScore>,
+}
+
+pub type BoxedStrategy = Box<dyn Strategy + Send + Sync>;
+type StrategyFactory = Box<dyn Fn(&StrategyConfig) -> BoxedStrategy + Send + Sync>;
+
+// Strategies known to the app, built into the StrategyManager on startup
+#[derive(Resource)]
+pub struct StrategyRegistry {
+    factories: Vec<StrategyFactory>,
+}
+
+impl Default for StrategyRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            factories: Vec::new(),
+        };
+
+        // Built-in strategies
+        registry.add(ExploreStrategy::new);
+        registry.add(GatherStrategy::new);
+        registry.add(DefendStrategy::new);
+        registry.add(AttackStrategy::new);
+
+        registry
+    }
+}
+
+impl StrategyRegistry {
+    pub fn add<S, F>(&mut self, factory: F)
+    where
+        S: Strategy + Send + Sync + 'static,
+        F: Fn(&StrategyConfig) -> S + Send + Sync + 'static,
+    {
+        self.factories
+            .push(Box::new(move |config: &StrategyConfig| -> BoxedStrategy {
+                Box::new(factory(config))
+            }));
+    }
+}
+
+// Lets any plugin add its own strategies: app.register_strategy(MyStrategy::new)
+pub trait StrategyAppExt {
+    fn register_strategy<S, F>(&mut self, factory: F) -> &mut Self
+    where
+        S: Strategy + Send + Sync + 'static,
+        F: Fn(&StrategyConfig) -> S + Send + Sync + 'static;
+}
+
+impl StrategyAppExt for App {
+    fn register_strategy<S, F>(&mut self, factory: F) -> &mut Self
+    where
+        S: Strategy + Send + Sync + 'static,
+        F: Fn(&StrategyConfig) -> S + Send + Sync + 'static,
+    {
+        self.init_resource::<StrategyRegistry>();
+        self.world_mut()
+            .resource_mut::<StrategyRegistry>()
+            .add(factory);
+        self
+    }
 }
 
 // Strategy manager to handle all strategies
 #[derive(Resource)]
 pub struct StrategyManager {
-    strategies: Vec<Box<dyn Strategy + Send + Sync>>,
-    ant_strategies: HashMap<String, String>, // Maps ant_id to current strategy name
+    strategies: Vec<BoxedStrategy>,
+    ant_plans: HashMap<String, AntPlan>, // Maps ant_id to its current plan
+    decisions: HashMap<String, StrategyDecision>, // Maps ant_id to its latest decision
+    // Turns an ant sticks to a freshly chosen strategy unless its plan is invalidated
+    commitment_turns: i32,
+    // Priority bonus the current strategy keeps once the commitment has expired
+    switch_hysteresis: f32,
+    // Health ratio below which committed plans are dropped and re-evaluated
+    low_health_ratio: f32,
+    // Projected health ratio below which ants abandon their plan and head home
+    retreat_health_ratio: f32,
+    // Per ant type switches, strategies missing here are enabled for everyone
+    enabled: BTreeMap<String, AntTypeToggles>,
 }
 
 impl Default for StrategyManager {
     fn default() -> Self {
-        let mut strategies: Vec<Box<dyn Strategy + Send + Sync>> = Vec::new();
-
-        // Add all strategies
-        strategies.push(Box::new(ExploreStrategy));
-        strategies.push(Box::new(GatherStrategy));
-        strategies.push(Box::new(DefendStrategy));
-        strategies.push(Box::new(AttackStrategy));
+        Self::from_registry(&StrategyRegistry::default(), &StrategyConfig::default())
+    }
+}
 
+impl StrategyManager {
+    pub fn new(config: &StrategyConfig) -> Self {
         Self {
-            strategies,
-            ant_strategies: HashMap::new(),
+            strategies: Vec::new(),
+            ant_plans: HashMap::new(),
+            decisions: HashMap::new(),
+            commitment_turns: config.commitment_turns,
+            switch_hysteresis: config.switch_hysteresis,
+            low_health_ratio: config.low_health_ratio,
+            retreat_health_ratio: config.retreat_health_ratio,
+            enabled: config.enabled.clone(),
         }
     }
-}
 
-impl StrategyManager {
-    // Calculate priorities for an ant and return the best strategy
-    pub fn select_strategy(&self, ant: &Ant, game_state: &GameState) -> &dyn Strategy {
-        let mut best_strategy = &self.strategies[0];
+    pub fn from_registry(registry: &StrategyRegistry, config: &StrategyConfig) -> Self {
+        let mut manager = Self::new(config);
+        for factory in &registry.factories {
+            manager.register(factory(config));
+        }
+        manager
+    }
+
+    // Add a strategy, replacing any registered under the same name
+    pub fn register(&mut self, strategy: BoxedStrategy) {
+        let name = strategy.name();
+        match self.strategies.iter().position(|s| s.name() == name) {
+            Some(index) => {
+                info!("Strategy '{}' replaced", name);
+                self.strategies[index] = strategy;
+            }
+            None => {
+                info!("Strategy '{}' registered", name);
+                self.strategies.push(strategy);
+            }
+        }
+    }
+
+    pub fn strategy_names(&self) -> Vec<&'static str> {
+        self.strategies
+            .iter()
+            .map(|strategy| strategy.name())
+            .collect()
+    }
+
+    pub fn retreat_health_ratio(&self) -> f32 {
+        self.retreat_health_ratio
+    }
+
+    pub fn is_enabled(&self, strategy_name: &str, ant_type: AntType) -> bool {
+        self.enabled
+            .get(strategy_name)
+            .is_none_or(|toggles| toggles.get(ant_type))
+    }
+
+    // Push new weights into every strategy without dropping plan memory
+    pub fn apply_config(&mut self, config: &StrategyConfig) {
+        self.commitment_turns = config.commitment_turns;
+        self.switch_hysteresis = config.switch_hysteresis;
+        self.low_health_ratio = config.low_health_ratio;
+        self.retreat_health_ratio = config.retreat_health_ratio;
+        self.enabled = config.enabled.clone();
+
+        for strategy in &mut self.strategies {
+            strategy.configure(config);
+        }
+    }
+
+    // Score every enabled strategy for an ant and pick the best one.
+    // Returns None when no strategy is enabled for the ant's type
+    pub fn select_strategy(
+        &self,
+        ant: &Ant,
+        game_state: &GameState,
+        influence: &InfluenceMap,
+    ) -> Option<(&dyn Strategy, StrategyDecision)> {
+        let current_plan = self.ant_plans.get(&ant.id);
+
+        let mut scores = Vec::new();
+        let mut best: Option<&dyn Strategy> = None;
         let mut highest_priority = f32::MIN;
 
         for strategy in &self.strategies {
+            if !self.is_enabled(strategy.name(), ant.ant_type) {
+                continue;
+            }
+
             // Calculate total priority
             let base = strategy.base_priority(ant.ant_type);
-            let global = strategy.global_priority_modifier(game_state);
-            let individual = strategy.individual_priority_modifier(ant, game_state);
-
-            let total_priority = base + global + individual;
+            let global = strategy.global_priority_modifier(game_state, influence);
+            let individual = strategy.individual_priority_modifier(ant, game_state, influence);
+
+            // Switching has a cost, so the current strategy keeps a small edge
+            let hysteresis = if current_plan.is_some_and(|plan| plan.strategy == strategy.name()) {
+                self.switch_hysteresis
+            } else {
+                0.0
+            };
+
+            let total_priority = base + global + individual + hysteresis;
+            scores.push(StrategyScore {
+                strategy: strategy.name(),
+                base,
+                global,
+                individual,
+                hysteresis,
+                total: total_priority,
+            });
 
             if total_priority > highest_priority {
                 highest_priority = total_priority;
-                best_strategy = strategy;
+                best = Some(strategy.as_ref());
             }
         }
 
-        best_strategy.as_ref()
+        // Stay committed to a recent decision
+        let committed = current_plan
+            .filter(|plan| game_state.turn_number < plan.committed_until)
+            .filter(|plan| self.is_enabled(plan.strategy, ant.ant_type))
+            .and_then(|plan| self.find_strategy(plan.strategy));
+
+        let strategy = committed.or(best)?;
+        let decision = StrategyDecision {
+            turn_number: game_state.turn_number,
+            chosen: strategy.name(),
+            committed: committed.is_some(),
+            scores,
+        };
+
+        Some((strategy, decision))
+    }
+
+    pub fn record_decision(&mut self, ant_id: &str, decision: StrategyDecision) {
+        self.decisions.insert(ant_id.to_string(), decision);
+    }
+
+    // Latest score breakdown for an ant
+    pub fn get_decision(&self, ant_id: &str) -> Option<&StrategyDecision> {
+        self.decisions.get(ant_id)
+    }
+
+    // Plan the ant can keep following, if it still runs the same strategy towards a goal
+    pub fn reusable_plan(&self, ant: &Ant, strategy_name: &str) -> Option<&AntPlan> {
+        self.ant_plans.get(&ant.id).filter(|plan| {
+            plan.strategy == strategy_name
+                && plan.goal.is_some_and(|goal| goal.is_persistent())
+                && !plan.route.is_empty()
+        })
+    }
+
+    // Remember the plan an ant has been given this turn
+    pub fn commit_plan(
+        &mut self,
+        ant: &Ant,
+        strategy_name: &'static str,
+        goal: Option<PlanGoal>,
+        route: Route,
+        turn_number: i32,
+    ) {
+        if let Some(plan) = self.ant_plans.get_mut(&ant.id) {
+            // A re-picked transient target is still the same plan
+            let transient = |goal: Option<PlanGoal>| goal.is_none_or(|goal| !goal.is_persistent());
+            if plan.strategy == strategy_name
+                && (plan.goal == goal || (transient(plan.goal) && transient(goal)))
+            {
+                plan.goal = goal;
+                plan.route = route;
+                plan.last_turn = turn_number;
+                plan.last_position = ant.position;
+                return;
+            }
+        }
+
+        self.ant_plans.insert(
+            ant.id.clone(),
+            AntPlan {
+                strategy: strategy_name,
+                goal,
+                route,
+                committed_until: turn_number + self.commitment_turns,
+                started_turn: turn_number,
+                last_turn: turn_number,
+                last_position: ant.position,
+            },
+        );
+    }
+
+    // Let every strategy refresh its shared per-turn state
+    pub fn prepare_turn(&mut self, game_state: &GameState, influence: &InfluenceMap) {
+        for strategy in &mut self.strategies {
+            strategy.prepare(game_state, influence);
+        }
+    }
+
+    // Advance plans along the new state and drop the ones that no longer make sense
+    pub fn update_plans(
+        &mut self,
+        game_state: &GameState,
+        influence: &InfluenceMap,
+    ) -> Vec<PlanInvalidatedEvent> {
+        let mut invalidated = Vec::new();
+        let low_health_ratio = self.low_health_ratio;
+
+        self.decisions
+            .retain(|ant_id, _| game_state.my_ants.contains_key(ant_id));
+
+        self.ant_plans.retain(|ant_id, plan| {
+            let reason = match game_state.my_ants.get(ant_id) {
+                None => Some(PlanInvalidation::AntDied),
+                Some(ant) => Self::check_plan(plan, ant, game_state, influence, low_health_ratio),
+            };
+
+            match reason {
+                Some(reason) => {
+                    invalidated.push(PlanInvalidatedEvent {
+                        ant_id: ant_id.clone(),
+                        strategy: plan.strategy.to_string(),
+                        reason,
+                    });
+                    false
+                }
+                None => true,
+            }
+        });
+
+        invalidated
+    }
+
+    pub fn get_ant_plan(&self, ant_id: &str) -> Option<&AntPlan> {
+        self.ant_plans.get(ant_id)
     }
 
-    // Track which strategy each ant is using
-    pub fn set_ant_strategy(&mut self, ant_id: &str, strategy_name: &str) {
-        self.ant_strategies
-            .insert(ant_id.to_string(), strategy_name.to_string());
+    pub fn get_ant_strategy(&self, ant_id: &str) -> Option<&'static str> {
+        self.ant_plans.get(ant_id).map(|plan| plan.strategy)
+    }
+
+    // Turns until the ant reaches its plan goal, if it has one
+    pub fn eta(&self, ant_id: &str) -> Option<usize> {
+        self.ant_plans
+            .get(ant_id)
+            .filter(|plan| plan.goal.is_some())
+            .map(|plan| plan.route.eta())
+    }
+
+    fn find_strategy(&self, name: &str) -> Option<&dyn Strategy> {
+        self.strategies
+            .iter()
+            .find(|strategy| strategy.name() == name)
+            .map(|strategy| strategy.as_ref() as &dyn Strategy)
+    }
+
+    fn check_plan(
+        plan: &mut AntPlan,
+        ant: &Ant,
+        game_state: &GameState,
+        influence: &InfluenceMap,
+        low_health_ratio: f32,
+    ) -> Option<PlanInvalidation> {
+        // Drop the part of the route the ant has already walked. An ant that moved but is not
+        // on the route was sent somewhere else, its next leg no longer starts next to it
+        let had_route = !plan.route.is_empty();
+        if !plan.route.advance_to(ant.position) && had_route && ant.position != plan.last_position {
+            return Some(PlanInvalidation::RouteChanged);
+        }
+
+        if let Some(goal) = plan.goal {
+            let target = goal.position();
+            if ant.position == target && goal.is_persistent() {
+                return Some(PlanInvalidation::GoalReached);
+            }
+
+            // Only trust the absence of food where we can actually see the hex
+            if let PlanGoal::Food(pos) = goal {
+                if game_state.visible_tiles.contains_key(&pos)
+                    && !game_state.food_on_map.contains_key(&pos)
+                {
+                    return Some(PlanInvalidation::TargetFoodGone);
+                }
+            }
+        }
+
+        let health_ratio = ant.health as f32 / ant.max_health.max(1) as f32;
+        if health_ratio < low_health_ratio && !matches!(plan.goal, Some(PlanGoal::Home(_))) {
+            return Some(PlanInvalidation::LowHealth);
+        }
+
+        if let Some(next) = plan.route.next_hex() {
+            if Self::is_hex_blocked(next, ant, game_state) {
+                return Some(PlanInvalidation::PathBlocked);
+            }
+        }
+
+        if plan.route.is_stale(game_state, influence) {
+            return Some(PlanInvalidation::RouteChanged);
+        }
+
+        // A whole turn passed with a path to follow but the ant did not move
+        if game_state.turn_number > plan.last_turn
+            && ant.position == plan.last_position
+            && !plan.route.is_empty()
+        {
+            return Some(PlanInvalidation::PathBlocked);
+        }
+
+        None
     }
 
-    pub fn get_ant_strategy(&self, ant_id: &str) -> Option<&String> {
-        self.ant_strategies.get(ant_id)
+    fn is_hex_blocked(pos: &HexCoord, ant: &Ant, game_state: &GameState) -> bool {
+        let impassable = game_state
+            .visible_tiles
+            .get(pos)
+            .is_some_and(|tile| !tile.tile_type.is_passable());
+        let enemy = game_state
+            .enemy_ants
+            .values()
+            .any(|enemy| enemy.position == *pos);
+        let same_type_friend = game_state.my_ants.values().any(|other| {
+            other.id != ant.id && other.ant_type == ant.ant_type && other.position == *pos
+        });
+
+        impassable || enemy || same_type_friend
     }
 }
 
 // Strategy types
-pub struct ExploreStrategy;
-pub struct GatherStrategy;
-pub struct DefendStrategy;
-pub struct AttackStrategy;
+pub struct ExploreStrategy {
+    weights: ExploreWeights,
+}
+
+pub struct GatherStrategy {
+    weights: GatherWeights,
+    low_health_ratio: f32,
+}
+
+pub struct DefendStrategy {
+    weights: DefendWeights,
+}
+
+pub struct AttackStrategy {
+    weights: AttackWeights,
+    squads: SquadPlanner,
+}
+
+impl ExploreStrategy {
+    pub fn new(config: &StrategyConfig) -> Self {
+        Self {
+            weights: config.explore.clone(),
+        }
+    }
+}
+
+impl GatherStrategy {
+    pub fn new(config: &StrategyConfig) -> Self {
+        Self {
+            weights: config.gather.clone(),
+            low_health_ratio: config.low_health_ratio,
+        }
+    }
+}
+
+impl DefendStrategy {
+    pub fn new(config: &StrategyConfig) -> Self {
+        Self {
+            weights: config.defend.clone(),
+        }
+    }
+}
+
+impl AttackStrategy {
+    pub fn new(config: &StrategyConfig) -> Self {
+        Self {
+            weights: config.attack.clone(),
+            squads: SquadPlanner::default(),
+        }
+    }
+}
 
 // Updated ExploreStrategy using the new movement system
 impl Strategy for ExploreStrategy {
@@ -89,34 +570,40 @@ impl Strategy for ExploreStrategy {
     }
 
     fn base_priority(&self, ant_type: AntType) -> f32 {
-        match ant_type {
-            AntType::Scout => 8.0,
-            AntType::Worker => 6.0,
-            AntType::Soldier => 3.0,
-        }
+        self.weights.base.get(ant_type)
     }
 
-    fn global_priority_modifier(&self, game_state: &GameState) -> f32 {
+    fn global_priority_modifier(&self, game_state: &GameState, _influence: &InfluenceMap) -> f32 {
+        let w = &self.weights;
         let visible_tile_count = game_state.visible_tiles.len();
         match visible_tile_count {
-            0..=30 => 7.0,
-            31..=80 => 4.0,
-            81..=150 => 2.0,
+            n if n <= w.few_tiles_threshold => w.few_tiles_bonus,
+            n if n <= w.some_tiles_threshold => w.some_tiles_bonus,
+            n if n <= w.many_tiles_threshold => w.many_tiles_bonus,
             _ => 0.0,
         }
     }
 
-    fn individual_priority_modifier(&self, ant: &Ant, game_state: &GameState) -> f32 {
+    fn individual_priority_modifier(
+        &self,
+        ant: &Ant,
+        game_state: &GameState,
+        _influence: &InfluenceMap,
+    ) -> f32 {
         let has_unexplored_neighbors = ant
             .position
             .neighbors()
             .iter()
             .any(|pos| !game_state.visible_tiles.contains_key(pos));
 
-        let frontier_bonus = if has_unexplored_neighbors { 4.0 } else { 0.0 };
+        let frontier_bonus = if has_unexplored_neighbors {
+            self.weights.frontier_bonus
+        } else {
+            0.0
+        };
 
         let movement_bonus = if !ant.current_move.is_empty() {
-            1.0
+            self.weights.movement_bonus
         } else {
             0.0
         };
@@ -128,25 +615,33 @@ impl Strategy for ExploreStrategy {
             .min()
             .unwrap_or(0) as f32);
 
-        let base_bonus = 25.0 / (distance_to_base * 5.0 + 1.0);
+        let base_bonus = self.weights.base_proximity_bonus
+            / (distance_to_base * self.weights.base_proximity_falloff + 1.0);
 
         frontier_bonus + movement_bonus + base_bonus
     }
 
-    fn execute(&self, ant: &Ant, game_state: &GameState) -> Vec<HexCoord> {
-        // Use the centralized movement system for exploration
-        let path = MovementManager::explore_move(ant, game_state);
+    fn goal(
+        &self,
+        ant: &Ant,
+        game_state: &GameState,
+        influence: &InfluenceMap,
+    ) -> Option<PlanGoal> {
+        let target = MovementManager::explore_target(ant, game_state, influence);
 
         info!(
-            "Explore: Ant {} (speed: {}) at {:?} planning {} moves: {:?}",
+            "Explore: Ant {} (speed: {}) at {:?} heading for {:?}",
             &ant.id[0..8],
             ant.ant_type.speed(),
             ant.position,
-            path.len(),
-            path
+            target
         );
 
-        path
+        target.map(PlanGoal::Explore)
+    }
+
+    fn configure(&mut self, config: &StrategyConfig) {
+        self.weights = config.explore.clone();
     }
 }
 
@@ -157,30 +652,45 @@ impl Strategy for GatherStrategy {
     }
 
     fn base_priority(&self, ant_type: AntType) -> f32 {
-        match ant_type {
-            AntType::Worker => 9.0,
-            AntType::Scout => 4.0,
-            AntType::Soldier => 2.0,
-        }
+        self.weights.base.get(ant_type)
     }
 
-    fn global_priority_modifier(&self, game_state: &GameState) -> f32 {
+    fn global_priority_modifier(&self, game_state: &GameState, _influence: &InfluenceMap) -> f32 {
+        let w = &self.weights;
         let food_count = game_state.food_on_map.len();
         match food_count {
-            n if n > 40 => 7.5,
-            n if n > 20 => 5.0,
-            n if n > 10 => 3.0,
-            n if n > 0 => 1.0,
+            n if n > w.high_food_threshold => w.high_food_bonus,
+            n if n > w.medium_food_threshold => w.medium_food_bonus,
+            n if n > w.low_food_threshold => w.low_food_bonus,
+            n if n > 0 => w.any_food_bonus,
             _ => 0.0,
         }
     }
 
-    fn individual_priority_modifier(&self, ant: &Ant, game_state: &GameState) -> f32 {
+    fn individual_priority_modifier(
+        &self,
+        ant: &Ant,
+        game_state: &GameState,
+        influence: &InfluenceMap,
+    ) -> f32 {
         let threshold = ant.ant_type.capacity();
         if ant.food.amount >= threshold {
-            return 20.0; // High priority to return home
+            return self.weights.return_bonus; // High priority to return home
         }
 
+        // Fresh loot nearby that this ant can carry, discounted by how dangerous it is
+        let loot_bonus = influence
+            .loot_piles()
+            .filter(|(pos, pile)| {
+                ant.position.distance_to(pos) <= self.weights.loot_radius
+                    && ant.can_pick_up(pile.food_type)
+            })
+            .map(|(pos, _)| {
+                let risk = influence.enemy_threat(pos) / ant.health.max(1) as f32;
+                self.weights.loot_bonus * (1.0 - risk).max(0.0)
+            })
+            .fold(0.0, f32::max);
+
         // Find the closest food not on home tiles
         if let Some(closest_food) = game_state
             .food_on_map
@@ -189,30 +699,54 @@ impl Strategy for GatherStrategy {
             .min_by_key(|food| ant.position.distance_to(&food.position))
         {
             let dist = ant.position.distance_to(&closest_food.position) as f32;
-            // Priority increases as food gets closer (tweak the formula as you like)
-            return 20.0 / (dist + 1.0) + (ant.food.amount as f32) * 5.0;
+            // Food the enemy controls is worth less than food we can safely pick up
+            let contested = (-influence.control(&closest_food.position)).max(0.0)
+                / self.weights.contested_food_divisor.max(1.0);
+            // Priority increases as food gets closer
+            return self.weights.proximity_bonus / (dist + 1.0)
+                + (ant.food.amount as f32) * self.weights.carried_food_weight
+                - contested
+                + loot_bonus;
         }
 
         // No food found
-        0.0
+        loot_bonus
     }
 
-    fn execute(&self, ant: &Ant, game_state: &GameState) -> Vec<HexCoord> {
+    fn goal(
+        &self,
+        ant: &Ant,
+        game_state: &GameState,
+        influence: &InfluenceMap,
+    ) -> Option<PlanGoal> {
+        if self.should_return_home(ant, game_state) {
+            MovementManager::nearest_home(ant, game_state).map(PlanGoal::Home)
+        } else {
+            MovementManager::best_food(ant, game_state, influence, &self.weights)
+                .map(|food| PlanGoal::Food(food.position))
+        }
+    }
+
+    fn configure(&mut self, config: &StrategyConfig) {
+        self.weights = config.gather.clone();
+        self.low_health_ratio = config.low_health_ratio;
+    }
+}
+
+impl GatherStrategy {
+    fn should_return_home(&self, ant: &Ant, game_state: &GameState) -> bool {
         let threshold = ant.ant_type.capacity();
         let ants_in_base = game_state
             .my_ants
             .values()
             .filter(|ant| game_state.home_tiles.contains(&ant.position))
             .count();
-        if ((ant.food.amount as f32) / (threshold as f32) >= 0.5 + 0.12 * (ants_in_base as f32))
-            || ((ant.health as f32) / (ant.ant_type.health() as f32) <= 0.3)
-        {
-            // Return to home if carrying food or low on health
-            MovementManager::return_to_home(ant, game_state)
-        } else {
-            // Go to nearest food
-            MovementManager::move_to_nearest_food(ant, game_state)
-        }
+
+        let fill_threshold = self.weights.return_fill_ratio
+            + self.weights.return_fill_per_ant_in_base * (ants_in_base as f32);
+
+        ((ant.food.amount as f32) / (threshold as f32) >= fill_threshold)
+            || ((ant.health as f32) / (ant.ant_type.health() as f32) <= self.low_health_ratio)
     }
 }
 
@@ -223,43 +757,62 @@ impl Strategy for DefendStrategy {
     }
 
     fn base_priority(&self, ant_type: AntType) -> f32 {
-        match ant_type {
-            AntType::Soldier => 8.0,
-            AntType::Worker => 3.0,
-            AntType::Scout => 2.0,
-        }
+        self.weights.base.get(ant_type)
     }
 
-    fn global_priority_modifier(&self, game_state: &GameState) -> f32 {
-        let enemies_near_home = game_state.enemy_ants.values().any(|enemy| {
-            game_state
-                .home_tiles
-                .iter()
-                .any(|home| enemy.position.distance_to(home) < 3)
-        });
+    fn global_priority_modifier(&self, game_state: &GameState, influence: &InfluenceMap) -> f32 {
+        // Enemies that can strike a home tile by the end of this turn
+        let enemies_near_home = game_state
+            .home_tiles
+            .iter()
+            .any(|home| influence.enemy_threat(home) > 0.0);
 
-        if enemies_near_home { 10.0 } else { 0.0 }
+        if enemies_near_home {
+            self.weights.enemies_near_home_bonus
+        } else {
+            0.0
+        }
     }
 
-    fn individual_priority_modifier(&self, _ant: &Ant, _game_state: &GameState) -> f32 {
+    fn individual_priority_modifier(
+        &self,
+        _ant: &Ant,
+        _game_state: &GameState,
+        _influence: &InfluenceMap,
+    ) -> f32 {
         0.0
     }
 
-    fn execute(&self, ant: &Ant, game_state: &GameState) -> Vec<HexCoord> {
-        // Find the most threatened home tile and move to defend it
-        if let Some(threatened_home) = game_state.home_tiles.iter().min_by_key(|home| {
-            game_state
-                .enemy_ants
-                .values()
-                .map(|enemy| enemy.position.distance_to(home))
-                .min()
-                .unwrap_or(i32::MAX)
-        }) {
-            MovementManager::move_to_defend(ant, *threatened_home, game_state)
-        } else {
-            // No specific threat, stay near main spot
-            MovementManager::move_to_defend(ant, game_state.main_spot, game_state)
-        }
+    fn goal(
+        &self,
+        _ant: &Ant,
+        game_state: &GameState,
+        _influence: &InfluenceMap,
+    ) -> Option<PlanGoal> {
+        Some(PlanGoal::Defend(Self::defend_position(game_state)))
+    }
+
+    fn configure(&mut self, config: &StrategyConfig) {
+        self.weights = config.defend.clone();
+    }
+}
+
+impl DefendStrategy {
+    fn defend_position(game_state: &GameState) -> HexCoord {
+        // Find the most threatened home tile, or stay near main spot without a specific threat
+        game_state
+            .home_tiles
+            .iter()
+            .min_by_key(|home| {
+                game_state
+                    .enemy_ants
+                    .values()
+                    .map(|enemy| enemy.position.distance_to(home))
+                    .min()
+                    .unwrap_or(i32::MAX)
+            })
+            .copied()
+            .unwrap_or(game_state.main_spot)
     }
 }
 
@@ -270,44 +823,97 @@ impl Strategy for AttackStrategy {
     }
 
     fn base_priority(&self, ant_type: AntType) -> f32 {
-        match ant_type {
-            AntType::Soldier => 7.0,
-            AntType::Scout => 3.0,
-            AntType::Worker => 1.0,
-        }
+        self.weights.base.get(ant_type)
     }
 
-    fn global_priority_modifier(&self, game_state: &GameState) -> f32 {
+    fn global_priority_modifier(&self, game_state: &GameState, _influence: &InfluenceMap) -> f32 {
         let soldier_count = game_state
             .my_ants
             .values()
             .filter(|ant| ant.ant_type == AntType::Soldier)
             .count();
 
-        if soldier_count > 3 { 5.0 } else { 0.0 }
+        if soldier_count > self.weights.min_soldiers {
+            self.weights.soldier_count_bonus
+        } else {
+            0.0
+        }
     }
 
-    fn individual_priority_modifier(&self, ant: &Ant, game_state: &GameState) -> f32 {
-        // Higher priority if ant is near an enemy
-        let near_enemy = game_state
-            .enemy_ants
-            .values()
-            .any(|enemy| ant.position.distance_to(&enemy.position) < 4);
+    fn individual_priority_modifier(
+        &self,
+        ant: &Ant,
+        _game_state: &GameState,
+        influence: &InfluenceMap,
+    ) -> f32 {
+        // Squads that can finish an enemy this turn should not get distracted
+        let focus_fire = match self.squads.order(&ant.id) {
+            Some(SquadOrder::Engage {
+                finishing: true, ..
+            }) => self.weights.focus_fire_bonus,
+            _ => 0.0,
+        };
 
-        if near_enemy { 3.0 } else { 0.0 }
-    }
+        // Higher priority if an enemy can reach the ant, more so if we have the upper hand
+        if influence.enemy_threat(&ant.position) <= 0.0 {
+            return focus_fire;
+        }
 
-    fn execute(&self, ant: &Ant, game_state: &GameState) -> Vec<HexCoord> {
-        // Find nearest enemy and attack
-        if let Some(nearest_enemy) = game_state
-            .enemy_ants
-            .values()
-            .min_by_key(|enemy| ant.position.distance_to(&enemy.position))
-        {
-            MovementManager::move_to_attack(ant, nearest_enemy, game_state)
+        if influence.control(&ant.position) > 0.0 {
+            self.weights.favorable_engagement_bonus + focus_fire
         } else {
-            // No enemies visible, explore to find them
-            MovementManager::explore_move(ant, game_state)
+            self.weights.unfavorable_engagement_bonus + focus_fire
         }
     }
+
+    fn goal(
+        &self,
+        ant: &Ant,
+        game_state: &GameState,
+        influence: &InfluenceMap,
+    ) -> Option<PlanGoal> {
+        let target = match self.squads.order(&ant.id) {
+            Some(SquadOrder::Engage { stand, .. }) => Some(stand),
+            Some(SquadOrder::Rally(point)) => {
+                // The rally hex is usually held by the squad member we are waiting for
+                let occupied = game_state
+                    .my_ants
+                    .values()
+                    .any(|other| other.id != ant.id && other.position == point);
+                if occupied {
+                    MovementManager::free_neighbor(ant, point, game_state)
+                } else {
+                    Some(point)
+                }
+            }
+            None => {
+                // Units cannot enter an enemy hex, so stand next to the nearest one
+                if let Some(nearest_enemy) = game_state
+                    .enemy_ants
+                    .values()
+                    .min_by_key(|enemy| ant.position.distance_to(&enemy.position))
+                {
+                    MovementManager::attack_stand(ant, nearest_enemy, game_state)
+                } else {
+                    // No enemies visible, explore to find them
+                    MovementManager::explore_target(ant, game_state, influence)
+                }
+            }
+        };
+
+        target.map(PlanGoal::Attack)
+    }
+
+    fn configure(&mut self, config: &StrategyConfig) {
+        self.weights = config.attack.clone();
+    }
+
+    fn prepare(&mut self, game_state: &GameState, influence: &InfluenceMap) {
+        self.squads.replan(
+            game_state,
+            influence,
+            self.weights.squad_size,
+            self.weights.assembly_radius,
+        );
+    }
 }
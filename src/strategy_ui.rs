@@ -0,0 +1,271 @@
+use crate::audit::ExecutionAuditor;
+use crate::config::*;
+use crate::menu::MenuState;
+use crate::strategy::StrategyManager;
+use crate::types::AntType;
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, egui};
+use std::path::Path;
+
+pub fn strategy_weights_ui_system(
+    mut contexts: EguiContexts,
+    mut menu_state: ResMut<MenuState>,
+    mut app_config: ResMut<AppConfig>,
+    strategy_manager: Option<Res<StrategyManager>>,
+    auditor: Option<Res<ExecutionAuditor>>,
+) -> Result {
+    if !menu_state.show_strategy_panel {
+        return Ok(());
+    }
+
+    let ctx = contexts.ctx_mut()?;
+
+    // Edit a copy so the config is only marked changed when a value actually moves
+    let mut strategy = app_config.strategy.clone();
+    let mut open = menu_state.show_strategy_panel;
+    let mut save_requested = false;
+
+    egui::Window::new("Strategy Weights")
+        .open(&mut open)
+        .default_width(420.0)
+        .resizable(true)
+        .vscroll(true)
+        .show(ctx, |ui| {
+            ui.collapsing("Enabled Strategies", |ui| {
+                let names = strategy_manager
+                    .as_ref()
+                    .map(|manager| manager.strategy_names())
+                    .unwrap_or_default();
+                strategy_toggles_grid(ui, &names, &mut strategy);
+            });
+
+            if let Some(auditor) = &auditor {
+                ui.collapsing("Plan Execution", |ui| {
+                    execution_stats_grid(ui, auditor);
+                });
+            }
+
+            ui.collapsing("Plan Commitment", |ui| {
+                ui.add(
+                    egui::Slider::new(&mut strategy.commitment_turns, 0..=10)
+                        .text("Commitment turns"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut strategy.switch_hysteresis, 0.0..=20.0)
+                        .text("Switch hysteresis"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut strategy.low_health_ratio, 0.0..=1.0)
+                        .text("Low health ratio"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut strategy.retreat_health_ratio, 0.0..=1.0)
+                        .text("Retreat health ratio"),
+                );
+            });
+
+            ui.collapsing("Explore", |ui| {
+                let w = &mut strategy.explore;
+                base_priority_sliders(ui, &mut w.base);
+                ui.separator();
+                ui.add(egui::Slider::new(&mut w.few_tiles_threshold, 0..=500).text("Few tiles ≤"));
+                ui.add(
+                    egui::Slider::new(&mut w.few_tiles_bonus, 0.0..=30.0).text("Few tiles bonus"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut w.some_tiles_threshold, 0..=500).text("Some tiles ≤"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut w.some_tiles_bonus, 0.0..=30.0).text("Some tiles bonus"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut w.many_tiles_threshold, 0..=1000).text("Many tiles ≤"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut w.many_tiles_bonus, 0.0..=30.0).text("Many tiles bonus"),
+                );
+                ui.add(egui::Slider::new(&mut w.frontier_bonus, 0.0..=30.0).text("Frontier bonus"));
+                ui.add(egui::Slider::new(&mut w.movement_bonus, 0.0..=30.0).text("Movement bonus"));
+                ui.add(
+                    egui::Slider::new(&mut w.base_proximity_bonus, 0.0..=100.0)
+                        .text("Base proximity bonus"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut w.base_proximity_falloff, 0.0..=20.0)
+                        .text("Base proximity falloff"),
+                );
+            });
+
+            ui.collapsing("Gather", |ui| {
+                let w = &mut strategy.gather;
+                base_priority_sliders(ui, &mut w.base);
+                ui.separator();
+                ui.add(egui::Slider::new(&mut w.high_food_threshold, 0..=200).text("High food >"));
+                ui.add(
+                    egui::Slider::new(&mut w.high_food_bonus, 0.0..=30.0).text("High food bonus"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut w.medium_food_threshold, 0..=200).text("Medium food >"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut w.medium_food_bonus, 0.0..=30.0)
+                        .text("Medium food bonus"),
+                );
+                ui.add(egui::Slider::new(&mut w.low_food_threshold, 0..=200).text("Low food >"));
+                ui.add(egui::Slider::new(&mut w.low_food_bonus, 0.0..=30.0).text("Low food bonus"));
+                ui.add(egui::Slider::new(&mut w.any_food_bonus, 0.0..=30.0).text("Any food bonus"));
+                ui.add(egui::Slider::new(&mut w.return_bonus, 0.0..=50.0).text("Full cargo bonus"));
+                ui.add(
+                    egui::Slider::new(&mut w.proximity_bonus, 0.0..=50.0).text("Proximity bonus"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut w.carried_food_weight, 0.0..=20.0)
+                        .text("Carried food weight"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut w.contested_food_divisor, 1.0..=500.0)
+                        .text("Contested food divisor"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut w.return_fill_ratio, 0.0..=1.0)
+                        .text("Return fill ratio"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut w.return_fill_per_ant_in_base, 0.0..=0.5)
+                        .text("Fill ratio per ant in base"),
+                );
+                ui.add(egui::Slider::new(&mut w.loot_weight, 1.0..=10.0).text("Loot value weight"));
+                ui.add(egui::Slider::new(&mut w.loot_radius, 0..=30).text("Loot radius"));
+                ui.add(egui::Slider::new(&mut w.loot_bonus, 0.0..=30.0).text("Loot bonus"));
+            });
+
+            ui.collapsing("Defend", |ui| {
+                let w = &mut strategy.defend;
+                base_priority_sliders(ui, &mut w.base);
+                ui.separator();
+                ui.add(
+                    egui::Slider::new(&mut w.enemies_near_home_bonus, 0.0..=50.0)
+                        .text("Enemies near home bonus"),
+                );
+            });
+
+            ui.collapsing("Attack", |ui| {
+                let w = &mut strategy.attack;
+                base_priority_sliders(ui, &mut w.base);
+                ui.separator();
+                ui.add(egui::Slider::new(&mut w.min_soldiers, 0..=30).text("Soldiers >"));
+                ui.add(
+                    egui::Slider::new(&mut w.soldier_count_bonus, 0.0..=30.0)
+                        .text("Soldier count bonus"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut w.favorable_engagement_bonus, 0.0..=30.0)
+                        .text("Favorable engagement bonus"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut w.unfavorable_engagement_bonus, 0.0..=30.0)
+                        .text("Unfavorable engagement bonus"),
+                );
+                ui.add(egui::Slider::new(&mut w.squad_size, 2..=6).text("Squad size"));
+                ui.add(egui::Slider::new(&mut w.assembly_radius, 0..=6).text("Assembly radius"));
+                ui.add(
+                    egui::Slider::new(&mut w.focus_fire_bonus, 0.0..=30.0).text("Focus fire bonus"),
+                );
+            });
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("Save to config.toml").clicked() {
+                    save_requested = true;
+                }
+                if ui.button("Reset to Defaults").clicked() {
+                    strategy = StrategyConfig::default();
+                }
+            });
+        });
+
+    if strategy != app_config.strategy {
+        app_config.strategy = strategy;
+    }
+
+    if save_requested {
+        if let Err(e) = app_config.save(Path::new(CONFIG_PATH)) {
+            error!("Failed to save strategy weights: {}", e);
+        } else {
+            info!("Strategy weights saved to {}", CONFIG_PATH);
+        }
+    }
+
+    menu_state.show_strategy_panel = open;
+
+    Ok(())
+}
+
+fn strategy_toggles_grid(ui: &mut egui::Ui, names: &[&'static str], strategy: &mut StrategyConfig) {
+    let ant_types = [AntType::Worker, AntType::Soldier, AntType::Scout];
+
+    egui::Grid::new("strategy_toggles")
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("Strategy");
+            for ant_type in ant_types {
+                ui.label(format!("{:?}", ant_type));
+            }
+            ui.end_row();
+
+            for name in names {
+                let mut toggles = strategy.enabled.get(*name).copied().unwrap_or_default();
+                ui.label(*name);
+                for ant_type in ant_types {
+                    ui.checkbox(toggles.get_mut(ant_type), "");
+                }
+                ui.end_row();
+
+                // Only strategies switched off for someone are written to the config
+                if toggles == AntTypeToggles::default() {
+                    strategy.enabled.remove(*name);
+                } else {
+                    strategy.enabled.insert(name.to_string(), toggles);
+                }
+            }
+        });
+}
+
+// How often each strategy's moves were carried out as planned
+fn execution_stats_grid(ui: &mut egui::Ui, auditor: &ExecutionAuditor) {
+    if auditor.stats().is_empty() {
+        ui.label("No moves audited yet");
+        return;
+    }
+
+    egui::Grid::new("execution_stats")
+        .striped(true)
+        .show(ui, |ui| {
+            for header in [
+                "Strategy", "Done", "Enemy", "Friendly", "MP", "Other", "Success",
+            ] {
+                ui.strong(header);
+            }
+            ui.end_row();
+
+            for (name, stats) in auditor.stats() {
+                ui.label(*name);
+                ui.label(stats.completed.to_string());
+                ui.label(stats.blocked_by_enemy.to_string());
+                ui.label(stats.blocked_by_friendly.to_string());
+                ui.label(stats.mp_exhausted.to_string());
+                ui.label(stats.blocked.to_string());
+                ui.label(format!("{:.0}%", stats.success_rate() * 100.0));
+                ui.end_row();
+            }
+        });
+}
+
+fn base_priority_sliders(ui: &mut egui::Ui, base: &mut BasePriorities) {
+    ui.label("Base priority:");
+    for ant_type in [AntType::Worker, AntType::Soldier, AntType::Scout] {
+        ui.add(
+            egui::Slider::new(base.get_mut(ant_type), 0.0..=20.0).text(format!("{:?}", ant_type)),
+        );
+    }
+}
@@ -0,0 +1,302 @@
+use crate::rendering::RenderingAssets;
+use crate::types::*;
+use bevy::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+use bevy::render::primitives::Aabb;
+use bevy::render::render_asset::RenderAssetUsages;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+// Hexes per chunk side, in offset coordinates
+pub const CHUNK_SIZE: i32 = 16;
+// Leaves a thin gap between neighbouring hexes
+const HEX_SCALE: f32 = 0.95;
+// Same size as the largest server maps
+pub const BENCHMARK_MAP_SIZE: i32 = 101;
+const BENCHMARK_RUNS: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChunkCoord {
+    pub q: i32,
+    pub r: i32,
+}
+
+impl ChunkCoord {
+    pub fn of(hex: HexCoord) -> Self {
+        Self {
+            q: hex.q.div_euclid(CHUNK_SIZE),
+            r: hex.r.div_euclid(CHUNK_SIZE),
+        }
+    }
+
+    fn hexes(&self) -> impl Iterator<Item = HexCoord> + use<> {
+        let (min_q, min_r) = (self.q * CHUNK_SIZE, self.r * CHUNK_SIZE);
+        (min_r..min_r + CHUNK_SIZE)
+            .flat_map(move |r| (min_q..min_q + CHUNK_SIZE).map(move |q| HexCoord::new(q, r)))
+    }
+}
+
+#[derive(Component)]
+pub struct TerrainChunk;
+
+// Tile colors, the fog of war variants are derived from the visible ones
+#[derive(Debug, Clone)]
+pub struct TerrainPalette {
+    visible: HashMap<TileType, Color>,
+    remembered: HashMap<TileType, Color>,
+    fog: Color,
+}
+
+impl TerrainPalette {
+    pub fn new(visible: HashMap<TileType, Color>, fog: Color) -> Self {
+        let remembered = visible
+            .iter()
+            .map(|(tile_type, color)| (*tile_type, remembered_color(*color)))
+            .collect();
+        Self {
+            visible,
+            remembered,
+            fog,
+        }
+    }
+
+    pub fn visible(&self, tile_type: TileType) -> Color {
+        self.visible
+            .get(&tile_type)
+            .copied()
+            .unwrap_or(Color::WHITE)
+    }
+
+    pub fn remembered(&self, tile_type: TileType) -> Color {
+        self.remembered
+            .get(&tile_type)
+            .copied()
+            .unwrap_or(Color::WHITE)
+    }
+
+    pub fn fog(&self) -> Color {
+        self.fog
+    }
+}
+
+fn remembered_color(color: Color) -> Color {
+    let srgba = color.to_srgba();
+    let gray = 0.3 * srgba.red + 0.59 * srgba.green + 0.11 * srgba.blue;
+    let fade = |channel: f32| (channel * 0.4 + gray * 0.6) * 0.55;
+    Color::srgb(fade(srgba.red), fade(srgba.green), fade(srgba.blue))
+}
+
+// The ground, drawn as one merged mesh per chunk. Setting a tile only marks its chunk dirty,
+// the mesh is rebuilt once in `rebuild`
+#[derive(Default)]
+pub struct TerrainChunks {
+    tiles: HashMap<HexCoord, Color>,
+    chunks: HashMap<ChunkCoord, Entity>,
+    dirty: HashSet<ChunkCoord>,
+}
+
+impl TerrainChunks {
+    pub fn contains(&self, hex: &HexCoord) -> bool {
+        self.tiles.contains_key(hex)
+    }
+
+    pub fn hexes(&self) -> impl Iterator<Item = HexCoord> + '_ {
+        self.tiles.keys().copied()
+    }
+
+    pub fn set(&mut self, hex: HexCoord, color: Color) {
+        if self.tiles.insert(hex, color) != Some(color) {
+            self.dirty.insert(ChunkCoord::of(hex));
+        }
+    }
+
+    // Rebuilds the dirty chunks, returns how many were rebuilt
+    pub fn rebuild(
+        &mut self,
+        commands: &mut Commands,
+        meshes: &mut Assets<Mesh>,
+        material: &Handle<StandardMaterial>,
+    ) -> usize {
+        let rebuilt = self.dirty.len();
+        for coord in self.dirty.drain() {
+            let Some(mesh) = build_chunk_mesh(coord, |hex| self.tiles.get(&hex).copied()) else {
+                continue;
+            };
+            // Bounds are only computed on spawn, so keep them in step with the mesh
+            let aabb = mesh.compute_aabb().unwrap_or_default();
+            let mesh = Mesh3d(meshes.add(mesh));
+
+            match self.chunks.get(&coord) {
+                Some(entity) => {
+                    commands.entity(*entity).insert((mesh, aabb));
+                }
+                None => {
+                    let entity = commands
+                        .spawn((
+                            mesh,
+                            aabb,
+                            MeshMaterial3d(material.clone()),
+                            Transform::default(),
+                            TerrainChunk,
+                        ))
+                        .id();
+                    self.chunks.insert(coord, entity);
+                }
+            }
+        }
+        rebuilt
+    }
+
+    pub fn despawn(&mut self, commands: &mut Commands) {
+        for entity in self.chunks.values() {
+            commands.entity(*entity).despawn();
+        }
+        *self = Self::default();
+    }
+}
+
+// One flat hexagon per known tile, colored per vertex. None if the chunk has no tiles
+fn build_chunk_mesh(
+    coord: ChunkCoord,
+    color_of: impl Fn(HexCoord) -> Option<Color>,
+) -> Option<Mesh> {
+    let tiles: Vec<(HexCoord, Color)> = coord
+        .hexes()
+        .filter_map(|hex| color_of(hex).map(|color| (hex, color)))
+        .collect();
+    if tiles.is_empty() {
+        return None;
+    }
+
+    let corners: [Vec3; 6] = std::array::from_fn(|i| {
+        let angle = std::f32::consts::PI / 3.0 * i as f32;
+        Vec3::new(angle.cos(), 0.0, angle.sin()) * HEX_SCALE
+    });
+
+    let mut positions = Vec::with_capacity(tiles.len() * 7);
+    let mut colors = Vec::with_capacity(tiles.len() * 7);
+    let mut uvs = Vec::with_capacity(tiles.len() * 7);
+    let mut indices = Vec::with_capacity(tiles.len() * 18);
+
+    for (hex, color) in tiles {
+        let center = hex_to_world_pos(&hex);
+        let color = LinearRgba::from(color).to_f32_array();
+        let base = positions.len() as u32;
+
+        positions.push(center.to_array());
+        uvs.push([0.5, 0.5]);
+        for corner in corners {
+            positions.push((center + corner).to_array());
+            uvs.push([0.5 + 0.5 * corner.x, 0.5 + 0.5 * corner.z]);
+        }
+        colors.extend(std::iter::repeat_n(color, 7));
+
+        for i in 0..6 {
+            let current = base + 1 + i;
+            let next = base + 1 + (i + 1) % 6;
+            indices.extend_from_slice(&[base, next, current]);
+        }
+    }
+
+    let normals = vec![[0.0, 1.0, 0.0]; positions.len()];
+    Some(
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::RENDER_WORLD,
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, colors)
+        .with_inserted_indices(Indices::U32(indices)),
+    )
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TerrainBenchmarkResult {
+    pub map_size: i32,
+    pub tiles: usize,
+    pub chunks: usize,
+    pub full_build: Duration, // Every chunk, as when the map is first seen or fog is toggled
+    pub chunk_rebuild: Duration, // One changed tile, as on a typical turn
+}
+
+// Set `requested` to run the benchmark on the next frame
+#[derive(Resource, Default)]
+pub struct TerrainBenchmark {
+    pub requested: bool,
+    pub last: Option<TerrainBenchmarkResult>,
+}
+
+// Times building the terrain meshes for a square map of made up tiles
+pub fn benchmark_terrain_build(map_size: i32, palette: &TerrainPalette) -> TerrainBenchmarkResult {
+    const TILE_TYPES: [TileType; 5] = [
+        TileType::Plain,
+        TileType::Dirt,
+        TileType::Acid,
+        TileType::Rock,
+        TileType::Anthill,
+    ];
+
+    let mut tiles = HashMap::new();
+    for q in 0..map_size {
+        for r in 0..map_size {
+            let tile_type = TILE_TYPES[(q * 7 + r * 13).rem_euclid(5) as usize];
+            let color = if (q + r) % 3 == 0 {
+                palette.remembered(tile_type)
+            } else {
+                palette.visible(tile_type)
+            };
+            tiles.insert(HexCoord::new(q, r), color);
+        }
+    }
+    let chunks: HashSet<ChunkCoord> = tiles.keys().map(|hex| ChunkCoord::of(*hex)).collect();
+
+    let start = Instant::now();
+    for _ in 0..BENCHMARK_RUNS {
+        for coord in &chunks {
+            std::hint::black_box(build_chunk_mesh(*coord, |hex| tiles.get(&hex).copied()));
+        }
+    }
+    let full_build = start.elapsed() / BENCHMARK_RUNS;
+
+    let changed = HexCoord::new(map_size / 2, map_size / 2);
+    tiles.insert(changed, palette.fog());
+    let start = Instant::now();
+    for _ in 0..BENCHMARK_RUNS {
+        std::hint::black_box(build_chunk_mesh(ChunkCoord::of(changed), |hex| {
+            tiles.get(&hex).copied()
+        }));
+    }
+    let chunk_rebuild = start.elapsed() / BENCHMARK_RUNS;
+
+    TerrainBenchmarkResult {
+        map_size,
+        tiles: tiles.len(),
+        chunks: chunks.len(),
+        full_build,
+        chunk_rebuild,
+    }
+}
+
+pub fn run_terrain_benchmark(
+    mut benchmark: ResMut<TerrainBenchmark>,
+    rendering_assets: Res<RenderingAssets>,
+) {
+    if !benchmark.requested {
+        return;
+    }
+    benchmark.requested = false;
+
+    let result = benchmark_terrain_build(BENCHMARK_MAP_SIZE, &rendering_assets.terrain_palette);
+    info!(
+        "Terrain benchmark: {}x{} map, {} tiles in {} chunks, full build {:.2?}, one chunk {:.2?}",
+        result.map_size,
+        result.map_size,
+        result.tiles,
+        result.chunks,
+        result.full_build,
+        result.chunk_rebuild
+    );
+    benchmark.last = Some(result);
+}
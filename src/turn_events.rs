@@ -0,0 +1,420 @@
+use crate::server::ServerTicker;
+use crate::types::*;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use chrono::Utc;
+use std::collections::HashMap;
+
+// No arena update for this long means the server has stopped answering
+const STALE_SECONDS: i64 = 15;
+
+// Everything that changed between two consecutive arena states
+#[derive(Debug, Default)]
+pub struct TurnDiff {
+    pub spawned: Vec<AntSpawnedEvent>,
+    pub died: Vec<AntDiedEvent>,
+    pub damaged: Vec<AntDamagedEvent>,
+    pub collected: Vec<FoodCollectedEvent>,
+    pub delivered: Vec<FoodDeliveredEvent>,
+    pub food_appeared: Vec<FoodAppearedEvent>,
+    pub food_disappeared: Vec<FoodDisappearedEvent>,
+    pub enemies_sighted: Vec<EnemySightedEvent>,
+    pub enemies_lost: Vec<EnemyLostEvent>,
+    pub score: Option<ScoreChangedEvent>,
+    pub interrupted: Vec<PathInterruptedEvent>,
+}
+
+impl TurnDiff {
+    // `planned` holds the paths we sent for the previous turn
+    pub fn between(
+        previous: &GameState,
+        current: &GameState,
+        planned: &HashMap<String, Vec<HexCoord>>,
+    ) -> Self {
+        let mut diff = Self::default();
+        diff.diff_ants(previous, current, planned);
+        diff.diff_food(previous, current);
+        diff.diff_enemies(previous, current);
+
+        if current.score != previous.score {
+            diff.score = Some(ScoreChangedEvent {
+                previous: previous.score,
+                score: current.score,
+            });
+        }
+
+        diff
+    }
+
+    fn diff_ants(
+        &mut self,
+        previous: &GameState,
+        current: &GameState,
+        planned: &HashMap<String, Vec<HexCoord>>,
+    ) {
+        for (id, ant) in &current.my_ants {
+            let Some(before) = previous.my_ants.get(id) else {
+                self.spawned.push(AntSpawnedEvent {
+                    ant_id: id.clone(),
+                    ant_type: ant.ant_type,
+                    position: ant.position,
+                });
+                continue;
+            };
+
+            if ant.health < before.health {
+                self.damaged.push(AntDamagedEvent {
+                    ant_id: id.clone(),
+                    position: ant.position,
+                    damage: before.health - ant.health,
+                    health: ant.health,
+                });
+            }
+
+            match (before.food(), ant.food()) {
+                (None, Some((food_type, amount))) => {
+                    self.collected.push(FoodCollectedEvent {
+                        ant_id: id.clone(),
+                        position: ant.position,
+                        food_type,
+                        amount,
+                    });
+                }
+                (Some((before_type, before_amount)), Some((food_type, amount)))
+                    if food_type == before_type && amount > before_amount =>
+                {
+                    self.collected.push(FoodCollectedEvent {
+                        ant_id: id.clone(),
+                        position: ant.position,
+                        food_type,
+                        amount: amount - before_amount,
+                    });
+                }
+                // Cargo only leaves the ant on the anthill, anywhere else it was not delivered
+                (Some((food_type, amount)), None) if current.home_tiles.contains(&ant.position) => {
+                    self.delivered.push(FoodDeliveredEvent {
+                        ant_id: id.clone(),
+                        position: ant.position,
+                        food_type,
+                        amount,
+                        calories: amount * food_type.calories(),
+                    });
+                }
+                _ => {}
+            }
+
+            if let Some(path) = planned.get(id).filter(|path| !path.is_empty()) {
+                // lastMove may or may not list the hex the ant started from
+                let walked: Vec<HexCoord> = ant
+                    .last_move
+                    .iter()
+                    .skip_while(|pos| **pos == before.position)
+                    .copied()
+                    .collect();
+                if walked.len() < path.len() {
+                    self.interrupted.push(PathInterruptedEvent {
+                        ant_id: id.clone(),
+                        planned: path.clone(),
+                        walked,
+                    });
+                }
+            }
+        }
+
+        for (id, ant) in &previous.my_ants {
+            if !current.my_ants.contains_key(id) {
+                self.died.push(AntDiedEvent {
+                    ant_id: id.clone(),
+                    ant_type: ant.ant_type,
+                    position: ant.position,
+                    cargo: ant.food(),
+                });
+            }
+        }
+    }
+
+    fn diff_food(&mut self, previous: &GameState, current: &GameState) {
+        for (pos, food) in &current.food_on_map {
+            let is_new = previous
+                .food_on_map
+                .get(pos)
+                .is_none_or(|before| before.food_type != food.food_type);
+            if is_new {
+                self.food_appeared.push(FoodAppearedEvent {
+                    position: *pos,
+                    food_type: food.food_type,
+                    amount: food.amount,
+                });
+            }
+        }
+
+        for (pos, food) in &previous.food_on_map {
+            // Food we can no longer see is not gone
+            if !current.visible_tiles.contains_key(pos) {
+                continue;
+            }
+            let gone = current
+                .food_on_map
+                .get(pos)
+                .is_none_or(|now| now.food_type != food.food_type);
+            if gone {
+                self.food_disappeared.push(FoodDisappearedEvent {
+                    position: *pos,
+                    food_type: food.food_type,
+                    amount: food.amount,
+                });
+            }
+        }
+    }
+
+    fn diff_enemies(&mut self, previous: &GameState, current: &GameState) {
+        let previous_enemies: Vec<&Enemy> = previous.enemy_ants.values().collect();
+        let mut current_enemies: Vec<&Enemy> = current.enemy_ants.values().collect();
+        current_enemies.sort_by_key(|enemy| (enemy.position.q, enemy.position.r));
+
+        let tracks: Vec<(AntType, HexCoord)> = previous_enemies
+            .iter()
+            .map(|enemy| (enemy.ant_type, enemy.position))
+            .collect();
+        let matches = match_enemies(&tracks, &current_enemies);
+
+        let sighted = current_enemies
+            .iter()
+            .zip(&matches)
+            .filter(|(_, matched)| matched.is_none())
+            .map(|(enemy, _)| *enemy);
+        let unmatched = previous_enemies
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !matches.contains(&Some(*index)))
+            .map(|(_, enemy)| *enemy);
+
+        self.enemies_sighted = sighted
+            .map(|enemy| EnemySightedEvent {
+                position: enemy.position,
+                ant_type: enemy.ant_type,
+                health: enemy.health,
+            })
+            .collect();
+        self.enemies_lost = unmatched
+            .map(|enemy| EnemyLostEvent {
+                position: enemy.position,
+                ant_type: enemy.ant_type,
+            })
+            .collect();
+    }
+}
+
+// Enemies have no ids, so match each one to the closest enemy of the same type it could
+// have walked to in one turn. Returns the index into `previous` for every current enemy,
+// or None if it was not seen before
+pub fn match_enemies(previous: &[(AntType, HexCoord)], current: &[&Enemy]) -> Vec<Option<usize>> {
+    let mut taken = vec![false; previous.len()];
+    let mut matches = Vec::with_capacity(current.len());
+
+    for enemy in current {
+        let matched = previous
+            .iter()
+            .enumerate()
+            .filter(|(index, (ant_type, position))| {
+                !taken[*index]
+                    && *ant_type == enemy.ant_type
+                    && position.distance_to(&enemy.position) <= enemy.ant_type.speed()
+            })
+            .min_by_key(|(_, (_, position))| position.distance_to(&enemy.position))
+            .map(|(index, _)| index);
+
+        if let Some(index) = matched {
+            taken[index] = true;
+        }
+        matches.push(matched);
+    }
+
+    matches
+}
+
+// Previous turn snapshot and the moves we sent during it
+#[derive(Resource, Default)]
+pub struct TurnTracker {
+    previous: Option<GameState>,
+    planned: HashMap<String, Vec<HexCoord>>,
+    planned_turn: i32,
+}
+
+#[derive(SystemParam)]
+pub struct TurnEventWriters<'w> {
+    spawned: EventWriter<'w, AntSpawnedEvent>,
+    died: EventWriter<'w, AntDiedEvent>,
+    damaged: EventWriter<'w, AntDamagedEvent>,
+    collected: EventWriter<'w, FoodCollectedEvent>,
+    delivered: EventWriter<'w, FoodDeliveredEvent>,
+    food_appeared: EventWriter<'w, FoodAppearedEvent>,
+    food_disappeared: EventWriter<'w, FoodDisappearedEvent>,
+    enemies_sighted: EventWriter<'w, EnemySightedEvent>,
+    enemies_lost: EventWriter<'w, EnemyLostEvent>,
+    score: EventWriter<'w, ScoreChangedEvent>,
+    interrupted: EventWriter<'w, PathInterruptedEvent>,
+}
+
+impl TurnEventWriters<'_> {
+    fn write(&mut self, diff: TurnDiff) {
+        self.spawned.write_batch(diff.spawned);
+        self.died.write_batch(diff.died);
+        self.damaged.write_batch(diff.damaged);
+        self.collected.write_batch(diff.collected);
+        self.delivered.write_batch(diff.delivered);
+        self.food_appeared.write_batch(diff.food_appeared);
+        self.food_disappeared.write_batch(diff.food_disappeared);
+        self.enemies_sighted.write_batch(diff.enemies_sighted);
+        self.enemies_lost.write_batch(diff.enemies_lost);
+        if let Some(score) = diff.score {
+            self.score.write(score);
+        }
+        self.interrupted.write_batch(diff.interrupted);
+    }
+}
+
+pub fn emit_turn_events(
+    game_state: Res<GameState>,
+    mut tracker: ResMut<TurnTracker>,
+    mut move_events: EventReader<MoveCommandEvent>,
+    mut writers: TurnEventWriters,
+) {
+    // Keep the latest path sent for each ant this turn
+    for event in move_events.read() {
+        if tracker.planned_turn != game_state.turn_number {
+            tracker.planned.clear();
+            tracker.planned_turn = game_state.turn_number;
+        }
+        tracker
+            .planned
+            .insert(event.ant_id.clone(), event.path.clone());
+    }
+
+    if !game_state.is_changed() || !game_state.connected {
+        return;
+    }
+
+    let Some(previous) = tracker.previous.as_ref() else {
+        tracker.previous = Some(game_state.clone());
+        return;
+    };
+    if previous.turn_number == game_state.turn_number {
+        return;
+    }
+
+    // A new round started, nothing to compare against
+    if game_state.turn_number < previous.turn_number {
+        *tracker = TurnTracker::default();
+        tracker.previous = Some(game_state.clone());
+        return;
+    }
+
+    let planned = if tracker.planned_turn == previous.turn_number {
+        std::mem::take(&mut tracker.planned)
+    } else {
+        HashMap::new()
+    };
+    let diff = TurnDiff::between(previous, &game_state, &planned);
+
+    debug!(
+        "Turn #{}: {} spawned, {} died, {} damaged, {} collected, {} delivered, {} paths interrupted",
+        game_state.turn_number,
+        diff.spawned.len(),
+        diff.died.len(),
+        diff.damaged.len(),
+        diff.collected.len(),
+        diff.delivered.len(),
+        diff.interrupted.len()
+    );
+
+    writers.write(diff);
+    tracker.previous = Some(game_state.clone());
+}
+
+// Last turn of the current round and whether its end was already announced
+#[derive(Resource, Default)]
+pub struct RoundTracker {
+    last_turn: Option<i32>,
+    ended: bool,
+}
+
+// A round is over when turnNo goes back, the server drops us into the lobby or stops
+// answering. Whichever comes first is announced, once
+pub fn detect_round_end(
+    game_state: Res<GameState>,
+    server_ticker: Option<Res<ServerTicker>>,
+    mut tracker: ResMut<RoundTracker>,
+    mut round_ended: EventWriter<RoundEndedEvent>,
+) {
+    let turn = game_state.turn_number;
+    if game_state.connected && tracker.last_turn != Some(turn) {
+        if let Some(last_turn) = tracker.last_turn.filter(|last| turn < *last) {
+            if !tracker.ended {
+                round_ended.write(RoundEndedEvent {
+                    end: RoundEnd::TurnReset,
+                    last_turn,
+                });
+            }
+        }
+        tracker.last_turn = Some(turn);
+        tracker.ended = false;
+        return;
+    }
+
+    let Some(last_turn) = tracker.last_turn.filter(|_| !tracker.ended) else {
+        return;
+    };
+    let lobby = server_ticker.is_some_and(|ticker| ticker.waiting_for_lobby);
+    let silent = !game_state.connected
+        || (Utc::now() - game_state.last_update).num_seconds() > STALE_SECONDS;
+    let end = if lobby {
+        RoundEnd::Lobby
+    } else if silent {
+        RoundEnd::ServerSilent
+    } else {
+        return;
+    };
+
+    info!("Round ended after turn {} ({:?})", last_turn, end);
+    round_ended.write(RoundEndedEvent { end, last_turn });
+    tracker.ended = true;
+}
+
+// Notable turn events in the game log
+pub fn log_turn_events(
+    mut died: EventReader<AntDiedEvent>,
+    mut delivered: EventReader<FoodDeliveredEvent>,
+    mut interrupted: EventReader<PathInterruptedEvent>,
+    mut score: EventReader<ScoreChangedEvent>,
+) {
+    for event in died.read() {
+        match event.cargo {
+            Some((food_type, amount)) => info!(
+                "Ant {} ({:?}) died at {:?} carrying {} {:?}",
+                event.ant_id, event.ant_type, event.position, amount, food_type
+            ),
+            None => info!(
+                "Ant {} ({:?}) died at {:?}",
+                event.ant_id, event.ant_type, event.position
+            ),
+        }
+    }
+    for event in delivered.read() {
+        info!(
+            "Ant {} delivered {} {:?} for {} calories",
+            event.ant_id, event.amount, event.food_type, event.calories
+        );
+    }
+    for event in interrupted.read() {
+        debug!(
+            "Ant {} walked {} of {} planned hexes",
+            event.ant_id,
+            event.walked.len(),
+            event.planned.len()
+        );
+    }
+    for event in score.read() {
+        debug!("Score {} -> {}", event.previous, event.score);
+    }
+}
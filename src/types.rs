@@ -65,6 +65,7 @@ pub struct ServerConfig {
     pub url: String,
     pub token: String,
     pub tick_rate: std::time::Duration,
+    pub logs_poll_rate: std::time::Duration,
     pub auto_reconnect: bool,
 }
 
@@ -87,6 +88,12 @@ impl Default for ConnectionState {
     }
 }
 
+// Ant currently picked for inspection
+#[derive(Debug, Clone, Default, Resource)]
+pub struct SelectedAnt {
+    pub ant_id: Option<String>,
+}
+
 // API Request/Response types for DatsPulse (with Api prefix)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiArenaResponse {
@@ -236,6 +243,12 @@ impl Ant {
             None
         }
     }
+
+    // Units carry one food type at a time and only up to their capacity
+    pub fn can_pick_up(&self, food_type: FoodType) -> bool {
+        self.food.amount < self.ant_type.capacity()
+            && (self.food.amount == 0 || self.food.food_type == food_type)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -401,6 +414,14 @@ impl AntType {
             AntType::Scout => 7,
         }
     }
+    // Chance the main anthill hex rolls this type when creating a unit
+    pub fn spawn_chance(&self) -> f32 {
+        match self {
+            AntType::Worker => 0.6,
+            AntType::Soldier => 0.3,
+            AntType::Scout => 0.1,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -646,6 +667,117 @@ pub struct MoveCommandEvent {
     pub path: Vec<HexCoord>,
 }
 
+#[derive(Event, Debug, Clone)]
+pub struct PlanInvalidatedEvent {
+    pub ant_id: String,
+    pub strategy: String,
+    pub reason: crate::strategy::PlanInvalidation,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct StrategyDecisionEvent {
+    pub ant_id: String,
+    pub ant_type: AntType,
+    pub position: HexCoord,
+    pub decision: crate::strategy::StrategyDecision,
+    pub path: Vec<HexCoord>,
+}
+
+// Turn diff events, emitted once per new turn by comparing it with the previous one
+#[derive(Event, Debug, Clone)]
+pub struct AntSpawnedEvent {
+    pub ant_id: String,
+    pub ant_type: AntType,
+    pub position: HexCoord,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct AntDiedEvent {
+    pub ant_id: String,
+    pub ant_type: AntType,
+    pub position: HexCoord,             // Last known position
+    pub cargo: Option<(FoodType, i32)>, // Last known cargo, dropped where the ant fell
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct AntDamagedEvent {
+    pub ant_id: String,
+    pub position: HexCoord,
+    pub damage: i32,
+    pub health: i32,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct FoodCollectedEvent {
+    pub ant_id: String,
+    pub position: HexCoord,
+    pub food_type: FoodType,
+    pub amount: i32,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct FoodDeliveredEvent {
+    pub ant_id: String,
+    pub position: HexCoord,
+    pub food_type: FoodType,
+    pub amount: i32,
+    pub calories: i32,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct FoodAppearedEvent {
+    pub position: HexCoord,
+    pub food_type: FoodType,
+    pub amount: i32,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct FoodDisappearedEvent {
+    pub position: HexCoord,
+    pub food_type: FoodType,
+    pub amount: i32,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct EnemySightedEvent {
+    pub position: HexCoord,
+    pub ant_type: AntType,
+    pub health: i32,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct EnemyLostEvent {
+    pub position: HexCoord, // Last known position
+    pub ant_type: AntType,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct ScoreChangedEvent {
+    pub previous: i32,
+    pub score: i32,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct PathInterruptedEvent {
+    pub ant_id: String,
+    pub planned: Vec<HexCoord>,
+    pub walked: Vec<HexCoord>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundEnd {
+    TurnReset,
+    Lobby,
+    ServerSilent,
+}
+
+// Sent once per round, in the frame the round is found to be over
+#[derive(Event, Debug, Clone, Copy)]
+pub struct RoundEndedEvent {
+    pub end: RoundEnd,
+    pub last_turn: i32,
+}
+
 // API Events
 #[derive(Event)]
 pub struct ApiArenaEvent(pub ApiArenaResponse);
@@ -656,6 +788,12 @@ pub struct ApiMoveEvent(pub ApiMoveRequest);
 #[derive(Event)]
 pub struct ApiRegistrationEvent(pub ApiRegistrationResponse);
 
+#[derive(Event)]
+pub struct ApiLogsEvent(pub Vec<ApiLogMessage>);
+
+#[derive(Event)]
+pub struct ApiMoveErrorsEvent(pub Vec<String>);
+
 // Components
 #[derive(Component)]
 pub struct Player;
@@ -696,6 +834,16 @@ pub struct AntMarker {
     pub is_enemy: bool,
 }
 
+// Latest numbers of a unit entity, shown by its HUD
+#[derive(Component, Debug, Clone)]
+pub struct UnitStats {
+    pub health: i32,
+    pub max_health: i32,
+    pub cargo: Option<(FoodType, i32)>,
+    pub capacity: i32,
+    pub attack: Option<i32>, // Only known for enemies
+}
+
 #[derive(Component)]
 pub struct FoodMarker {
     pub food_type: FoodType,
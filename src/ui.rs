@@ -1,3 +1,4 @@
+use crate::colony::SpawnTracker;
 use crate::menu::MenuState;
 use crate::types::*;
 use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
@@ -172,6 +173,7 @@ pub fn update_debug_text(menu_state: Res<MenuState>, mut query: Query<&mut Text,
 
 pub fn update_game_state_text(
     game_state: Res<GameState>,
+    spawn_tracker: Res<SpawnTracker>,
     mut query: Query<&mut Text, With<GameStateText>>,
 ) {
     for mut text in &mut query {
@@ -200,15 +202,26 @@ pub fn update_game_state_text(
                 carrying_food += ant.food.amount;
             }
 
+            // Observed spawn shares next to the 60/30/10% odds
+            let spawn_share = |ant_type: AntType| {
+                format!(
+                    "{:.0}%/{:.0}%",
+                    spawn_tracker.observed_share(ant_type) * 100.0,
+                    ant_type.spawn_chance() * 100.0
+                )
+            };
+
             text.0 = format!(
                 "Turn: {} | Score: {} | Next turn: {:.1}s
-Ants: {} (W:{} S:{} Sc:{}) | Enemies: {} | Food: {}
+Ants: {}/{} (W:{} S:{} Sc:{}) | Enemies: {} | Food: {}
 Carrying: {} | Visible tiles: {}
-Home: ({}, {})",
+Home: ({}, {})
+Spawned: {} (W:{} S:{} Sc:{}) | Missed: {} (expected {:.1})",
                 game_state.turn_number,
                 game_state.score,
                 game_state.next_turn_in,
                 ant_count,
+                MAX_ANTS,
                 worker_count,
                 soldier_count,
                 scout_count,
@@ -217,7 +230,13 @@ Home: ({}, {})",
                 carrying_food,
                 visible_tiles,
                 game_state.main_spot.q,
-                game_state.main_spot.r
+                game_state.main_spot.r,
+                spawn_tracker.total_spawned(),
+                spawn_share(AntType::Worker),
+                spawn_share(AntType::Soldier),
+                spawn_share(AntType::Scout),
+                spawn_tracker.missed(),
+                spawn_tracker.expected_misses()
             );
         } else {
             text.0 = "Game State: Disconnected".to_string();
@@ -0,0 +1,324 @@
+use crate::types::*;
+use bevy::pbr::NotShadowCaster;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+const HUD_HEIGHT: f32 = 0.9; // Above the unit's origin
+const BAR_WIDTH: f32 = 0.8;
+const BAR_HEIGHT: f32 = 0.08;
+const THIN_BAR_HEIGHT: f32 = 0.04;
+const ICON_SIZE: f32 = 0.16;
+// Past this camera distance the HUDs are hidden
+const HUD_HIDE_DISTANCE: f32 = 30.0;
+// Soldier attack fills the enemy attack bar
+const ATTACK_BAR_FULL: f32 = 70.0;
+
+#[derive(Resource)]
+pub struct HudAssets {
+    quad: Handle<Mesh>,
+    badge_meshes: HashMap<AntType, Handle<Mesh>>,
+    badge_materials: HashMap<AntType, Handle<StandardMaterial>>,
+    cargo_materials: HashMap<FoodType, Handle<StandardMaterial>>,
+    background: Handle<StandardMaterial>,
+    enemy_background: Handle<StandardMaterial>,
+    health_high: Handle<StandardMaterial>,
+    health_mid: Handle<StandardMaterial>,
+    health_low: Handle<StandardMaterial>,
+    attack: Handle<StandardMaterial>,
+}
+
+// Billboard floating above a unit
+#[derive(Component)]
+pub struct UnitHud;
+
+#[derive(Component)]
+pub struct HudPart;
+
+struct HudParts {
+    root: Entity,
+    health_fill: Entity,
+    cargo_icon: Entity,
+    cargo_fill: Entity,
+    attack_fill: Entity,
+}
+
+fn flat_material(
+    materials: &mut Assets<StandardMaterial>,
+    color: Color,
+) -> Handle<StandardMaterial> {
+    materials.add(StandardMaterial {
+        base_color: color,
+        unlit: true,
+        alpha_mode: if color.alpha() < 1.0 {
+            AlphaMode::Blend
+        } else {
+            AlphaMode::Opaque
+        },
+        ..default()
+    })
+}
+
+pub fn setup_unit_hud_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Each type gets its own shape, so badges read without relying on color alone
+    let mut badge_meshes = HashMap::new();
+    badge_meshes.insert(AntType::Worker, meshes.add(Rectangle::new(1.0, 1.0)));
+    badge_meshes.insert(AntType::Soldier, meshes.add(Rhombus::new(1.2, 1.2)));
+    badge_meshes.insert(AntType::Scout, meshes.add(RegularPolygon::new(0.6, 3)));
+
+    // Same tints as the ant models
+    let mut badge_materials = HashMap::new();
+    badge_materials.insert(
+        AntType::Worker,
+        flat_material(&mut materials, Color::srgb(0.9, 0.9, 0.9)),
+    );
+    badge_materials.insert(
+        AntType::Soldier,
+        flat_material(&mut materials, Color::srgb(0.0, 1.0, 0.0)),
+    );
+    badge_materials.insert(
+        AntType::Scout,
+        flat_material(&mut materials, Color::srgb(0.2, 0.4, 1.0)),
+    );
+
+    // Same colors as the food on the map
+    let mut cargo_materials = HashMap::new();
+    cargo_materials.insert(
+        FoodType::Apple,
+        flat_material(&mut materials, Color::srgb(0.9, 0.1, 0.1)),
+    );
+    cargo_materials.insert(
+        FoodType::Bread,
+        flat_material(&mut materials, Color::srgb(0.9, 0.7, 0.3)),
+    );
+    cargo_materials.insert(
+        FoodType::Nectar,
+        flat_material(&mut materials, Color::srgb(1.0, 0.9, 0.1)),
+    );
+
+    commands.insert_resource(HudAssets {
+        quad: meshes.add(Rectangle::new(1.0, 1.0)),
+        badge_meshes,
+        badge_materials,
+        cargo_materials,
+        background: flat_material(&mut materials, Color::srgba(0.1, 0.1, 0.1, 0.7)),
+        enemy_background: flat_material(&mut materials, Color::srgba(0.4, 0.05, 0.05, 0.7)),
+        health_high: flat_material(&mut materials, Color::srgb(0.2, 0.9, 0.2)),
+        health_mid: flat_material(&mut materials, Color::srgb(1.0, 0.8, 0.1)),
+        health_low: flat_material(&mut materials, Color::srgb(1.0, 0.2, 0.1)),
+        attack: flat_material(&mut materials, Color::srgb(1.0, 0.5, 0.0)),
+    });
+}
+
+fn spawn_hud(commands: &mut Commands, assets: &HudAssets, marker: &AntMarker) -> HudParts {
+    let part = |mesh: &Handle<Mesh>, material: &Handle<StandardMaterial>, transform: Transform| {
+        (
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(material.clone()),
+            transform,
+            HudPart,
+            NotShadowCaster,
+        )
+    };
+    let background = if marker.is_enemy {
+        &assets.enemy_background
+    } else {
+        &assets.background
+    };
+    let side = BAR_WIDTH / 2.0 + ICON_SIZE;
+    let thin_offset = BAR_HEIGHT / 2.0 + THIN_BAR_HEIGHT;
+
+    let mut health_fill = Entity::PLACEHOLDER;
+    let mut cargo_icon = Entity::PLACEHOLDER;
+    let mut cargo_fill = Entity::PLACEHOLDER;
+    let mut attack_fill = Entity::PLACEHOLDER;
+    let root = commands
+        .spawn((Transform::default(), Visibility::Hidden, UnitHud))
+        .with_children(|parent| {
+            parent.spawn(part(
+                &assets.quad,
+                background,
+                Transform::from_scale(Vec3::new(
+                    BAR_WIDTH + 0.04,
+                    BAR_HEIGHT + 2.0 * THIN_BAR_HEIGHT + 0.08,
+                    1.0,
+                )),
+            ));
+            health_fill = parent
+                .spawn(part(
+                    &assets.quad,
+                    &assets.health_high,
+                    Transform::from_xyz(0.0, 0.0, 0.002),
+                ))
+                .id();
+            attack_fill = parent
+                .spawn(part(
+                    &assets.quad,
+                    &assets.attack,
+                    Transform::from_xyz(0.0, thin_offset, 0.002),
+                ))
+                .id();
+            cargo_fill = parent
+                .spawn(part(
+                    &assets.quad,
+                    &assets.background,
+                    Transform::from_xyz(0.0, -thin_offset, 0.002),
+                ))
+                .id();
+            cargo_icon = parent
+                .spawn(part(
+                    &assets.quad,
+                    &assets.background,
+                    Transform::from_xyz(side, 0.0, 0.002).with_scale(Vec3::splat(ICON_SIZE)),
+                ))
+                .id();
+            if let (Some(mesh), Some(material)) = (
+                assets.badge_meshes.get(&marker.ant_type),
+                assets.badge_materials.get(&marker.ant_type),
+            ) {
+                parent.spawn(part(
+                    mesh,
+                    material,
+                    Transform::from_xyz(-side, 0.0, 0.002).with_scale(Vec3::splat(ICON_SIZE)),
+                ));
+            }
+        })
+        .id();
+
+    HudParts {
+        root,
+        health_fill,
+        cargo_icon,
+        cargo_fill,
+        attack_fill,
+    }
+}
+
+type HudPartQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Transform,
+        &'static mut Visibility,
+        &'static mut MeshMaterial3d<StandardMaterial>,
+    ),
+    (With<HudPart>, Without<UnitHud>),
+>;
+
+// Stretches a bar from its left edge, hiding it when empty
+fn set_bar(
+    parts: &mut HudPartQuery,
+    entity: Entity,
+    ratio: f32,
+    height: f32,
+    material: Option<&Handle<StandardMaterial>>,
+) {
+    let Ok((mut transform, mut visibility, mut mesh_material)) = parts.get_mut(entity) else {
+        return;
+    };
+    let ratio = ratio.clamp(0.0, 1.0);
+    *visibility = if ratio > 0.0 {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    transform.scale = Vec3::new(BAR_WIDTH * ratio, height, 1.0);
+    transform.translation.x = -BAR_WIDTH / 2.0 + BAR_WIDTH * ratio / 2.0;
+    if let Some(material) = material.filter(|material| mesh_material.0 != **material) {
+        mesh_material.0 = material.clone();
+    }
+}
+
+pub fn update_unit_huds(
+    mut commands: Commands,
+    assets: Res<HudAssets>,
+    mut huds: Local<HashMap<Entity, HudParts>>,
+    units: Query<(Entity, &GlobalTransform, &AntMarker, &UnitStats)>,
+    cameras: Query<&GlobalTransform, With<GameCamera>>,
+    mut roots: Query<(&mut Transform, &mut Visibility), (With<UnitHud>, Without<HudPart>)>,
+    mut parts: HudPartQuery,
+) -> Result {
+    let camera = cameras.single()?;
+    let camera_rotation = camera.compute_transform().rotation;
+
+    // HUDs of units that are gone
+    huds.retain(|unit, hud| {
+        let alive = units.contains(*unit);
+        if !alive {
+            commands.entity(hud.root).despawn();
+        }
+        alive
+    });
+
+    for (unit, transform, marker, stats) in &units {
+        let hud = huds
+            .entry(unit)
+            .or_insert_with(|| spawn_hud(&mut commands, &assets, marker));
+        // A HUD spawned this frame is placed on the next one
+        let Ok((mut root_transform, mut root_visibility)) = roots.get_mut(hud.root) else {
+            continue;
+        };
+
+        // Level of detail: zoomed out, the bars are too small to read
+        let position = transform.translation() + Vec3::Y * HUD_HEIGHT;
+        if camera.translation().distance(position) > HUD_HIDE_DISTANCE {
+            *root_visibility = Visibility::Hidden;
+            continue;
+        }
+        *root_visibility = Visibility::Inherited;
+        root_transform.translation = position;
+        root_transform.rotation = camera_rotation;
+
+        let health = stats.health as f32 / stats.max_health.max(1) as f32;
+        let health_material = if health > 0.6 {
+            &assets.health_high
+        } else if health > 0.3 {
+            &assets.health_mid
+        } else {
+            &assets.health_low
+        };
+        set_bar(
+            &mut parts,
+            hud.health_fill,
+            health,
+            BAR_HEIGHT,
+            Some(health_material),
+        );
+
+        let attack = stats
+            .attack
+            .map_or(0.0, |attack| attack as f32 / ATTACK_BAR_FULL);
+        set_bar(&mut parts, hud.attack_fill, attack, THIN_BAR_HEIGHT, None);
+
+        // Cargo: icon in the food's color, bar for amount over capacity
+        let cargo_material = stats
+            .cargo
+            .and_then(|(food_type, _)| assets.cargo_materials.get(&food_type));
+        let cargo = stats.cargo.map_or(0.0, |(_, amount)| {
+            amount as f32 / stats.capacity.max(1) as f32
+        });
+        set_bar(
+            &mut parts,
+            hud.cargo_fill,
+            cargo,
+            THIN_BAR_HEIGHT,
+            cargo_material,
+        );
+        if let Ok((_, mut visibility, mut mesh_material)) = parts.get_mut(hud.cargo_icon) {
+            match cargo_material {
+                Some(material) => {
+                    *visibility = Visibility::Inherited;
+                    if mesh_material.0 != *material {
+                        mesh_material.0 = material.clone();
+                    }
+                }
+                None => *visibility = Visibility::Hidden,
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -1,30 +1,20 @@
+use crate::config::GatherWeights;
+use crate::influence::InfluenceMap;
+use crate::route::Route;
 use crate::types::*;
 use bevy::prelude::*;
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+// Damage worth taking to get one hex further along a path this turn
+const STOP_PROGRESS_VALUE: f32 = 2.0;
+// Hexes expanded before giving up on a target walled off by known rock
+const MAX_SEARCH_NODES: usize = 20_000;
 
 // Enhanced movement system that respects speed limits and provides common movement patterns
 pub struct MovementManager;
 
 impl MovementManager {
-    /// Find a path to target, respecting the ant's speed limit
-    pub fn find_path_to_target(
-        ant: &Ant,
-        target: HexCoord,
-        game_state: &GameState,
-    ) -> Vec<HexCoord> {
-        let max_moves = ant.ant_type.speed() as usize;
-
-        if let Some(path) = Self::pathfind(ant.position, target, &game_state.visible_tiles) {
-            // Return only the moves the ant can make this turn (excluding current position)
-            path.into_iter()
-                .skip(1) // Skip current position
-                .take(max_moves)
-                .collect()
-        } else {
-            Vec::new()
-        }
-    }
-
     /// Get all valid adjacent moves for an ant
     pub fn get_valid_moves(ant: &Ant, game_state: &GameState) -> Vec<HexCoord> {
         ant.position
@@ -34,81 +24,166 @@ impl MovementManager {
             .collect()
     }
 
-    /// Move towards a target, respecting speed limits
-    pub fn move_towards(ant: &Ant, target: HexCoord, game_state: &GameState) -> Vec<HexCoord> {
-        Self::find_path_to_target(ant, target, game_state)
-    }
-
-    /// Find a good exploration move (prioritizes unexplored areas)
-    pub fn explore_move(ant: &Ant, game_state: &GameState) -> Vec<HexCoord> {
+    /// Frontier hex worth heading for, a turn's worth of greedy steps into the unexplored
+    pub fn explore_target(
+        ant: &Ant,
+        game_state: &GameState,
+        influence: &InfluenceMap,
+    ) -> Option<HexCoord> {
         let max_moves = ant.ant_type.speed() as usize;
-        let valid_moves = Self::get_valid_moves(ant, game_state);
-
-        if valid_moves.is_empty() {
-            return Vec::new();
-        }
-
-        // Score moves based on exploration value
-        let mut scored_moves: Vec<(HexCoord, f32)> = valid_moves
-            .iter()
-            .map(|pos| (*pos, Self::exploration_score(*pos, game_state)))
-            .collect();
-
-        // Sort by score (highest first)
-        scored_moves.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-
-        // Try to plan a multi-step exploration path
-        if let Some((best_move, _)) = scored_moves.first() {
-            Self::plan_exploration_path(ant.position, *best_move, max_moves, game_state)
-        } else {
-            Vec::new()
-        }
+        let first_move = Self::get_valid_moves(ant, game_state)
+            .into_iter()
+            .max_by(|a, b| {
+                Self::exploration_score(*a, game_state, influence)
+                    .partial_cmp(&Self::exploration_score(*b, game_state, influence))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })?;
+
+        Self::plan_exploration_path(ant.position, first_move, max_moves, game_state, influence)
+            .last()
+            .copied()
     }
 
-    pub fn move_to_nearest_food(ant: &Ant, game_state: &GameState) -> Vec<HexCoord> {
-        let nearest_food = game_state
+    /// Pick the food worth walking to: close, calorie-rich and not contested by the enemy
+    pub fn best_food<'a>(
+        ant: &Ant,
+        game_state: &'a GameState,
+        influence: &InfluenceMap,
+        weights: &GatherWeights,
+    ) -> Option<&'a FoodOnMap> {
+        game_state
             .food_on_map
             .values()
             .filter(|food| !game_state.home_tiles.contains(&food.position)) // Ignore food at home
-            .min_by_key(|food| ant.position.distance_to(&food.position));
-
-        if let Some(food) = nearest_food {
-            Self::find_path_to_target(ant, food.position, game_state)
-        } else {
-            Vec::new()
-        }
+            .max_by(|a, b| {
+                Self::food_score(ant, a, influence, weights)
+                    .partial_cmp(&Self::food_score(ant, b, influence, weights))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
     }
 
-    /// Return to the nearest home tile
-    pub fn return_to_home(ant: &Ant, game_state: &GameState) -> Vec<HexCoord> {
-        let nearest_home = game_state
+    pub fn nearest_home(ant: &Ant, game_state: &GameState) -> Option<HexCoord> {
+        // Units standing on the main spot block spawns, so the other anthill hexes win ties
+        // and may even be one hex further away
+        game_state
             .home_tiles
             .iter()
-            .min_by_key(|home| ant.position.distance_to(home));
-
-        if let Some(home) = nearest_home {
-            Self::find_path_to_target(ant, *home, game_state)
-        } else {
-            Vec::new()
-        }
+            .min_by_key(|home| {
+                let on_main_spot = **home == game_state.main_spot;
+                (
+                    ant.position.distance_to(home) + on_main_spot as i32,
+                    on_main_spot,
+                )
+            })
+            .copied()
     }
 
-    /// Move to intercept or attack an enemy
-    pub fn move_to_attack(
+    /// Full path to target over as many turns as it takes (excluding current position)
+    pub fn full_path(
         ant: &Ant,
-        target_enemy: &Enemy,
+        target: HexCoord,
         game_state: &GameState,
+        influence: &InfluenceMap,
     ) -> Vec<HexCoord> {
-        Self::find_path_to_target(ant, target_enemy.position, game_state)
+        Self::pathfind(ant.position, target, game_state, influence)
+            .map(|path| path.into_iter().skip(1).collect())
+            .unwrap_or_default()
     }
 
-    /// Move to defend a specific position
-    pub fn move_to_defend(
+    /// Full route to target split into per-turn legs by movement points
+    pub fn plan_route(
         ant: &Ant,
-        defend_position: HexCoord,
+        target: HexCoord,
         game_state: &GameState,
+        influence: &InfluenceMap,
+    ) -> Route {
+        let path = Self::full_path(ant, target, game_state, influence);
+        Route::segment(&path, ant.ant_type.speed(), game_state, influence)
+    }
+
+    /// Decide where along this turn's path to stop. Acid and anthill auras only hurt on the
+    /// hex a unit ends its turn on, so hazards crossed mid-path cost nothing
+    pub fn hazard_aware_stop(
+        ant: &Ant,
+        path: Vec<HexCoord>,
+        game_state: &GameState,
+        influence: &InfluenceMap,
     ) -> Vec<HexCoord> {
-        Self::find_path_to_target(ant, defend_position, game_state)
+        let mut best_len = 0;
+        let mut best_score = -influence.end_of_turn_damage(&ant.position);
+
+        for (index, pos) in path.iter().enumerate() {
+            let score =
+                (index + 1) as f32 * STOP_PROGRESS_VALUE - influence.end_of_turn_damage(pos);
+            if score >= best_score {
+                best_score = score;
+                best_len = index + 1;
+            }
+        }
+
+        if best_len > 0 || influence.end_of_turn_damage(&ant.position) <= 0.0 {
+            return path.into_iter().take(best_len).collect();
+        }
+
+        // Standing still hurts, step onto the safest neighbour instead
+        Self::get_valid_moves(ant, game_state)
+            .into_iter()
+            .filter(|pos| {
+                !game_state
+                    .enemy_ants
+                    .values()
+                    .any(|enemy| enemy.position == *pos)
+            })
+            .min_by(|a, b| {
+                influence
+                    .end_of_turn_damage(a)
+                    .partial_cmp(&influence.end_of_turn_damage(b))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .filter(|pos| {
+                influence.end_of_turn_damage(pos) < influence.end_of_turn_damage(&ant.position)
+            })
+            .map(|pos| vec![pos])
+            .unwrap_or_default()
+    }
+
+    /// Health left after standing on this hex at the end of the turn, assuming every
+    /// enemy that can reach it attacks
+    pub fn projected_health(ant: &Ant, stop: HexCoord, influence: &InfluenceMap) -> f32 {
+        ant.health as f32 - influence.end_of_turn_damage(&stop) - influence.enemy_threat(&stop)
+    }
+
+    /// Free hex next to an enemy to attack it from, None if the ant is already next to it
+    pub fn attack_stand(
+        ant: &Ant,
+        target_enemy: &Enemy,
+        game_state: &GameState,
+    ) -> Option<HexCoord> {
+        // Attacks reach adjacent hexes, head for the closest free one around the enemy
+        if target_enemy.position.neighbors().contains(&ant.position) {
+            return None;
+        }
+
+        Self::free_neighbor(ant, target_enemy.position, game_state)
+    }
+
+    /// Closest hex around `center` the ant can stand on, not held by another unit
+    pub fn free_neighbor(ant: &Ant, center: HexCoord, game_state: &GameState) -> Option<HexCoord> {
+        center
+            .neighbors()
+            .into_iter()
+            .filter(|pos| Self::is_valid_move(pos, game_state))
+            .filter(|pos| {
+                !game_state
+                    .enemy_ants
+                    .values()
+                    .any(|enemy| enemy.position == *pos)
+                    && !game_state
+                        .my_ants
+                        .values()
+                        .any(|other| other.id != ant.id && other.position == *pos)
+            })
+            .min_by_key(|pos| ant.position.distance_to(pos))
     }
 
     // Private helper methods
@@ -120,7 +195,28 @@ impl MovementManager {
         }
     }
 
-    fn exploration_score(pos: HexCoord, game_state: &GameState) -> f32 {
+    fn food_score(
+        ant: &Ant,
+        food: &FoodOnMap,
+        influence: &InfluenceMap,
+        weights: &GatherWeights,
+    ) -> f32 {
+        let distance = ant.position.distance_to(&food.position);
+        let threat_penalty = influence.enemy_threat(&food.position) / ant.health.max(1) as f32;
+        let mut value = influence.food_value(&food.position).max(1.0);
+
+        // Dropped cargo near a fight is often nectar, worth a detour for nearby ants with room
+        if influence.loot(&food.position).is_some()
+            && distance <= weights.loot_radius
+            && ant.can_pick_up(food.food_type)
+        {
+            value *= weights.loot_weight;
+        }
+
+        value / (distance as f32 + 1.0) * (1.0 - threat_penalty).max(0.1)
+    }
+
+    fn exploration_score(pos: HexCoord, game_state: &GameState, influence: &InfluenceMap) -> f32 {
         let mut score = 0.0;
 
         // Count unexplored neighbors
@@ -141,6 +237,10 @@ impl MovementManager {
 
         score -= nearby_ants as f32 * 0.5;
 
+        // Avoid hexes the enemy can strike and hexes that hurt to stand on
+        score -= influence.enemy_threat(&pos) / 50.0;
+        score -= influence.end_of_turn_damage(&pos) / 10.0;
+
         score
     }
 
@@ -149,6 +249,7 @@ impl MovementManager {
         first_move: HexCoord,
         max_moves: usize,
         game_state: &GameState,
+        influence: &InfluenceMap,
     ) -> Vec<HexCoord> {
         let mut path = vec![first_move];
         let mut current = first_move;
@@ -168,8 +269,8 @@ impl MovementManager {
             let next_move = valid_moves
                 .iter()
                 .max_by(|a, b| {
-                    Self::exploration_score(**a, game_state)
-                        .partial_cmp(&Self::exploration_score(**b, game_state))
+                    Self::exploration_score(**a, game_state, influence)
+                        .partial_cmp(&Self::exploration_score(**b, game_state, influence))
                         .unwrap_or(std::cmp::Ordering::Equal)
                 })
                 .cloned();
@@ -185,57 +286,64 @@ impl MovementManager {
         path
     }
 
+    // Cheapest path by movement points, start and target included. Unknown hexes are assumed
+    // to be plain
     fn pathfind(
         start: HexCoord,
         target: HexCoord,
-        tiles: &HashMap<HexCoord, Tile>,
+        game_state: &GameState,
+        influence: &InfluenceMap,
     ) -> Option<Vec<HexCoord>> {
-        // Simple BFS pathfinding
-        let mut queue = VecDeque::new();
-        let mut visited = HashSet::new();
+        let mut frontier = BinaryHeap::new();
+        let mut best_cost: HashMap<HexCoord, i32> = HashMap::new();
         let mut came_from = HashMap::new();
 
-        queue.push_back(start);
-        visited.insert(start);
+        frontier.push(Reverse((0, start.q, start.r)));
+        best_cost.insert(start, 0);
 
-        while let Some(current) = queue.pop_front() {
+        while let Some(Reverse((cost, q, r))) = frontier.pop() {
+            let current = HexCoord::new(q, r);
             if current == target {
-                // Reconstruct path
-                let mut path = Vec::new();
+                let mut path = vec![target];
                 let mut current = target;
-
-                while current != start {
+                while let Some(parent) = came_from.get(&current) {
+                    current = *parent;
                     path.push(current);
-                    if let Some(parent) = came_from.get(&current) {
-                        current = *parent;
-                    } else {
-                        break;
-                    }
                 }
-
-                path.push(start);
                 path.reverse();
                 return Some(path);
             }
 
+            // A cheaper way here was already expanded
+            if best_cost.get(&current).is_some_and(|known| cost > *known) {
+                continue;
+            }
+            if best_cost.len() > MAX_SEARCH_NODES {
+                break;
+            }
+
             for neighbor in current.neighbors() {
-                if !visited.contains(&neighbor) && Self::is_tile_passable(&neighbor, tiles) {
-                    visited.insert(neighbor);
+                // Hexes our ants recently failed to enter are avoided unless they are the target
+                let learned_obstacle =
+                    neighbor != target && influence.is_learned_obstacle(&neighbor);
+                if learned_obstacle || !Self::is_valid_move(&neighbor, game_state) {
+                    continue;
+                }
+
+                let next_cost = cost + Route::step_cost(&neighbor, game_state);
+                if best_cost
+                    .get(&neighbor)
+                    .is_none_or(|known| next_cost < *known)
+                {
+                    best_cost.insert(neighbor, next_cost);
                     came_from.insert(neighbor, current);
-                    queue.push_back(neighbor);
+                    frontier.push(Reverse((next_cost, neighbor.q, neighbor.r)));
                 }
             }
         }
 
         None
     }
-
-    fn is_tile_passable(pos: &HexCoord, tiles: &HashMap<HexCoord, Tile>) -> bool {
-        match tiles.get(pos) {
-            Some(tile) => tile.tile_type.is_passable(),
-            None => true, // Assume unexplored tiles are passable
-        }
-    }
 }
 pub struct PathFinder;
 